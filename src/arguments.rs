@@ -1,65 +1,319 @@
-pub struct ProjectArguments(pub String);
+#[derive(Debug, Default, Clone)]
+pub struct ProjectArguments {
+    pub name: String,
+    pub author: String,
+    pub email: String,
+    pub license: String,
+    pub description: String,
 
+    /// Python version, e.g. `3.12`; empty means the version of `python3`
+    pub python: String,
+
+    /// Package layout, `src` or `flat`
+    pub layout: String,
+
+    /// README format, `md` or `rst`
+    pub readme_format: String,
+
+    /// Use the existing project directory, e.g. `createpy init`; `name` defaults to the
+    /// current directory
+    pub in_place: bool,
+}
+
+impl ProjectArguments {
+    pub fn new(name: String) -> ProjectArguments {
+        ProjectArguments {
+            name,
+            author: String::new(),
+            email: String::new(),
+            license: String::new(),
+            description: String::new(),
+            python: String::new(),
+            layout: String::new(),
+            readme_format: String::new(),
+            in_place: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct GitArguments {
     pub user_name: String,
     pub user_email: String,
     pub remote_url: String,
     pub remote_name: String,
     pub branch: String,
+
+    /// Other remotes, `name=url`
+    pub remotes: Vec<String>,
+
+    /// Push URLs of the remotes, `name=url`
+    pub push_urls: Vec<String>,
+
+    /// URL style of remote shorthands, `ssh` or `https`; see [`crate::config::RemotesConfig`]
+    pub remote_style: String,
+
+    /// Repository configuration entries, `key=value`; applied in order
+    pub config: Vec<String>,
+
+    /// Create a `.gitattributes` file
+    pub attributes: bool,
+
+    /// Submodules, `path=url`
+    pub submodules: Vec<String>,
+
+    /// Commit the generated files after all steps
+    pub commit: bool,
 }
 
 impl GitArguments {
-    fn new() -> GitArguments {
+    pub fn new() -> GitArguments {
         GitArguments {
             user_name: String::new(),
             user_email: String::new(),
             remote_url: String::new(),
             remote_name: String::new(),
             branch: String::new(),
+            remotes: Vec::new(),
+            push_urls: Vec::new(),
+            remote_style: String::new(),
+            config: Vec::new(),
+            attributes: false,
+            submodules: Vec::new(),
+            commit: false,
         }
     }
 }
 
+#[derive(Debug, Default, Clone)]
 pub struct VirtualEnvironmentArguments {
+    /// Project manager, `pip`, `poetry`, `pdm` or `hatch`; `script` is used only by `pip`
+    pub manager: String,
+
+    /// `virtualenv`, `venv` or `native`
     pub script: String,
+
+    /// Virtual environment path, relative to the current directory; project managers use
+    /// `.venv` in the project directory
     pub path: String,
+
+    /// Don't install pip into the virtual environment; used only by `pip` manager
+    pub without_pip: bool,
 }
 
 impl VirtualEnvironmentArguments {
-    fn new() -> VirtualEnvironmentArguments {
+    pub fn new() -> VirtualEnvironmentArguments {
         VirtualEnvironmentArguments {
+            manager: String::new(),
             script: String::new(),
             path: String::new(),
+            without_pip: false,
         }
     }
 }
 
+#[derive(Debug, Default, Clone)]
+pub struct ToolingArguments {
+    /// Test framework, `pytest`, `unittest` or `none`
+    pub tests: String,
+
+    /// Linter preset, `ruff`, `black+isort`, `flake8` or empty
+    pub lint: String,
+
+    /// Type checker, `mypy`, `pyright` or empty
+    pub typecheck: String,
+
+    /// Type checking strictness, `basic` or `strict`
+    pub strictness: String,
+
+    /// Configure pre-commit hooks; requires git and virtual environment
+    pub pre_commit: bool,
+
+    /// CI service, `github`, `gitlab`, `gitea`, `auto` (inferred from the git remote) or empty
+    pub ci: String,
+
+    /// Python versions of the CI matrix; defaults to the project python version
+    pub ci_python: Vec<String>,
+
+    /// Dependency tool of the Dockerfile, `pip`, `uv`, `poetry` or empty (no Dockerfile)
+    pub docker: String,
+
+    /// Create `compose.yaml`; requires `docker`
+    pub compose: bool,
+
+    /// Lock tool, `pip` (`pip freeze`), `uv` (`uv pip compile`), `manager` (the project
+    /// manager lock command), `auto` or empty
+    pub lock: String,
+}
+
+impl ToolingArguments {
+    pub fn new() -> ToolingArguments {
+        ToolingArguments {
+            tests: String::new(),
+            lint: String::new(),
+            typecheck: String::new(),
+            strictness: String::new(),
+            pre_commit: false,
+            ci: String::new(),
+            ci_python: Vec::new(),
+            docker: String::new(),
+            compose: false,
+            lock: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct HooksArguments {
+    pub pre_create: Vec<String>,
+    pub post_create: Vec<String>,
+}
+
+impl HooksArguments {
+    pub fn new() -> HooksArguments {
+        HooksArguments {
+            pre_create: Vec::new(),
+            post_create: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct OtherArguments {
-    pub reinitialize_without_input: bool
+    pub reinitialize_without_input: bool,
+    pub template: String,
+    pub only: Vec<String>,
+    pub skip: Vec<String>,
 }
 
 impl OtherArguments {
-    fn new() -> OtherArguments {
+    pub fn new() -> OtherArguments {
         OtherArguments {
             reinitialize_without_input: false,
+            template: String::new(),
+            only: Vec::new(),
+            skip: Vec::new(),
         }
     }
 }
 
-pub struct Arguments(
-    pub ProjectArguments,
-    pub Option<GitArguments>,
-    pub Option<VirtualEnvironmentArguments>,
-    pub OtherArguments
-);
+pub struct Arguments {
+    pub project: ProjectArguments,
+    pub git: Option<GitArguments>,
+    pub venv: Option<VirtualEnvironmentArguments>,
+    pub tools: ToolingArguments,
+    pub hooks: HooksArguments,
+    pub other: OtherArguments,
+}
 
-#[inline(always)]
-fn create_commands() -> clap::ArgMatches {
-    clap::Command::new(env!("CARGO_PKG_NAME"))
-        .version(env!("CARGO_PKG_VERSION"))
-        .arg_required_else_help(true)
-        .arg(clap::Arg::new("projectname").required(true))
+impl Arguments {
+    pub fn new(project_name: String) -> Arguments {
+        Arguments {
+            project: ProjectArguments::new(project_name),
+            git: None,
+            venv: None,
+            tools: ToolingArguments::new(),
+            hooks: HooksArguments::new(),
+            other: OtherArguments::new(),
+        }
+    }
+}
+
+/// Arguments of `add-package`; the package is added to the project in `root`
+#[derive(Debug, Default, Clone)]
+pub struct PackageArguments {
+    pub name: String,
+    pub description: String,
+
+    /// Root project directory; defaults to the current directory
+    pub root: String,
+
+    /// Template of the config file, whose hooks are run
+    pub template: String,
+    pub hooks: HooksArguments,
+
+    pub reinitialize_without_input: bool,
+}
+
+impl PackageArguments {
+    pub fn new(name: String) -> PackageArguments {
+        PackageArguments {
+            name,
+            description: String::new(),
+            root: String::new(),
+            template: String::new(),
+            hooks: HooksArguments::new(),
+            reinitialize_without_input: false,
+        }
+    }
+}
+
+/// Parsed command line
+pub enum Command {
+    /// Create a project, or add components to it; `new`, `init` and `add`
+    Project(Box<Arguments>),
+
+    /// Add a workspace package to an existing project
+    AddPackage(PackageArguments),
+
+    /// Check the environment
+    Doctor,
+
+    /// List the configured templates
+    Templates,
+
+    /// Show the configuration file
+    Config,
+}
+
+/// Components of `createpy add`; they're the steps which are performed
+const COMPONENTS: [&str; 4] = ["git", "venv", "ci", "license"];
+
+/// Add the project options to `command`; they're shared by `new`, `init` and `add`
+fn project_options(command: clap::Command) -> clap::Command {
+    command
         .next_line_help(true)
+        // project arguments
+        .next_help_heading("Project")
+        .args(
+            [
+                clap::Arg::new("description")
+                    .short('d')
+                    .long("description")
+                    .value_name("text")
+                    .help("A short description of the project, used in the metadata and README."),
+
+                clap::Arg::new("python")
+                    .long("python")
+                    .value_name("version")
+                    .help("Python version of the project, e.g. '3.12'; defaults to the version of 'python3'."),
+
+                clap::Arg::new("layout")
+                    .long("layout")
+                    .default_value("src")
+                    .value_parser(["src", "flat"])
+                    .help("Package layout; 'src' puts the package in the 'src' directory."),
+
+                clap::Arg::new("readmeformat")
+                    .long("readme-format")
+                    .default_value("md")
+                    .value_parser(["md", "rst"])
+                    .help("Create README.md or README.rst."),
+
+                clap::Arg::new("author")
+                    .long("author")
+                    .value_name("name")
+                    .help("Author name, used in the license and metadata; defaults to the git user name."),
+
+                clap::Arg::new("license")
+                    .long("license")
+                    .value_name("SPDX")
+                    .value_parser(clap::builder::PossibleValuesParser::new(
+                        crate::template::LICENSES.map(|(id, _)| id),
+                    ))
+                    .help("Create a LICENSE file and set the license in the project metadata."),
+            ]
+        )
         // git arguments
         .next_help_heading("Git")
         .args(
@@ -86,7 +340,7 @@ fn create_commands() -> clap::ArgMatches {
                     .short('r')
                     .long("git-remote-url")
                     .value_name("URL")
-                    .help("Adding new remote to the created git repository; this option do nothing without '-g'."),
+                    .help("Adding new remote to the created git repository; shorthands like 'gh:org/repo', 'gl:group' or 'org/repo' are expanded. this option do nothing without '-g'."),
                 
                 clap::Arg::new("gitremotename")
                     .long("git-remote-name")
@@ -99,7 +353,46 @@ fn create_commands() -> clap::ArgMatches {
                     .long("git-branch")
                     .default_value("main")
                     .value_name("branch")
-                    .help("A branch name for the empty git repository; this option do nothing without '-g'.")
+                    .help("A branch name for the empty git repository; this option do nothing without '-g'."),
+
+                clap::Arg::new("gitremote")
+                    .long("remote")
+                    .value_name("name=url")
+                    .action(clap::ArgAction::Append)
+                    .help("Add a remote to the created git repository; can be repeated, e.g. for mirrors. this option do nothing without '-g'."),
+
+                clap::Arg::new("gitpushurl")
+                    .long("push-url")
+                    .value_name("name=url")
+                    .action(clap::ArgAction::Append)
+                    .help("Set the push URL of a remote, if it differs from the fetch URL; this option do nothing without '-g'."),
+
+                clap::Arg::new("gitremotestyle")
+                    .long("remote-style")
+                    .value_parser(["ssh", "https"])
+                    .help("URL style of remote shorthands, e.g. 'gh:org/repo' or 'gh:org'; defaults to the config file, or 'ssh'."),
+
+                clap::Arg::new("gitconfig")
+                    .long("git-config")
+                    .value_name("key=value")
+                    .action(clap::ArgAction::Append)
+                    .help("Set a configuration of the created git repository, e.g. 'pull.rebase=true'; can be repeated, and is applied in order. this option do nothing without '-g'."),
+
+                clap::Arg::new("gitattributes")
+                    .long("gitattributes")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Create a '.gitattributes' file for line endings, notebooks and binary files; this option do nothing without '-g'."),
+
+                clap::Arg::new("gitsubmodule")
+                    .long("submodule")
+                    .value_name("path=url")
+                    .action(clap::ArgAction::Append)
+                    .help("Add a submodule to the created git repository, e.g. 'config/common=gh:org/common-config'; can be repeated. this option do nothing without '-g'."),
+
+                clap::Arg::new("gitcommit")
+                    .long("initial-commit")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Commit the generated files after all steps; this option do nothing without '-g'."),
             ]
         )
         // virtualenv arguments
@@ -112,6 +405,13 @@ fn create_commands() -> clap::ArgMatches {
                     .action(clap::ArgAction::SetFalse)
                     .help("Disable virtual environment creation for project."),
                 
+                clap::Arg::new("venvmanager")
+                    .short('m')
+                    .long("manager")
+                    .default_value("pip")
+                    .value_parser(["pip", "poetry", "pdm", "hatch"])
+                    .help("Project manager; 'poetry', 'pdm' and 'hatch' create an in-project '.venv' and ignore '-p' and '-s'."),
+
                 clap::Arg::new("venvpath")
                     .short('p')
                     .long("venv-path")
@@ -124,7 +424,83 @@ fn create_commands() -> clap::ArgMatches {
                     .long("venv-script")
                     .default_value("virtualenv")
                     .value_name("name")
-                    .help("Use which script for creating virtual environment? 'virtualenv' and 'venv' are supported."),
+                    .help("Use which script for creating virtual environment? 'virtualenv', 'venv' and 'native' are supported; 'native' creates it without any script."),
+
+                clap::Arg::new("venvwithoutpip")
+                    .long("without-pip")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Don't install pip into the virtual environment; dev dependencies cannot be installed without it."),
+            ]
+        )
+        // tooling arguments
+        .next_help_heading("Tooling")
+        .args(
+            [
+                clap::Arg::new("tests")
+                    .long("tests")
+                    .default_value("none")
+                    .value_parser(["pytest", "unittest", "none"])
+                    .help("Create a 'tests' package with a smoke test, and run it once after creating the project."),
+
+                clap::Arg::new("lint")
+                    .long("lint")
+                    .value_name("preset")
+                    .value_parser(["ruff", "black+isort", "flake8"])
+                    .help("Configure linters and formatters, and install them into the virtual environment."),
+
+                clap::Arg::new("typecheck")
+                    .long("typecheck")
+                    .value_name("checker")
+                    .value_parser(["mypy", "pyright"])
+                    .help("Configure a type checker, and mark the package as typed with 'py.typed'."),
+
+                clap::Arg::new("strictness")
+                    .long("strictness")
+                    .default_value("basic")
+                    .value_parser(["basic", "strict"])
+                    .help("Type checking strictness; this option do nothing without '--typecheck'."),
+
+                clap::Arg::new("precommit")
+                    .long("pre-commit")
+                    .action(clap::ArgAction::SetTrue)
+                    .help("Write '.pre-commit-config.yaml' and install the git hooks; requires '-g' and the virtual environment."),
+
+                clap::Arg::new("ci")
+                    .long("ci")
+                    .value_name("service")
+                    .num_args(0..=1)
+                    .default_missing_value("auto")
+                    .value_parser(["github", "gitlab", "gitea", "auto"])
+                    .help("Create a CI workflow; the service is inferred from the git remote URL if it's not specified."),
+
+                clap::Arg::new("cipython")
+                    .long("ci-python")
+                    .value_name("versions")
+                    .value_delimiter(',')
+                    .action(clap::ArgAction::Append)
+                    .help("Python versions of the CI matrix (comma-separated); defaults to the project python version."),
+
+                clap::Arg::new("docker")
+                    .long("docker")
+                    .value_name("tool")
+                    .num_args(0..=1)
+                    .default_missing_value("auto")
+                    .value_parser(["pip", "uv", "poetry", "auto"])
+                    .help("Create a multi-stage Dockerfile and .dockerignore; the tool installs dependencies (default: the project manager)."),
+
+                clap::Arg::new("compose")
+                    .long("compose")
+                    .action(clap::ArgAction::SetTrue)
+                    .requires("docker")
+                    .help("Create compose.yaml with a service named after the project; requires '--docker'."),
+
+                clap::Arg::new("lock")
+                    .long("lock")
+                    .value_name("tool")
+                    .num_args(0..=1)
+                    .default_missing_value("auto")
+                    .value_parser(["pip", "uv", "manager", "auto"])
+                    .help("Lock the installed dependencies; 'pip' and 'uv' write 'requirements.lock', 'manager' runs the project manager lock command (default: the project manager, or pip)."),
             ]
         )
         // Create a custom version and help flag
@@ -138,6 +514,27 @@ fn create_commands() -> clap::ArgMatches {
                     .long("yes")
                     .action(clap::ArgAction::SetTrue)
                     .help("Automatic yes to prompts; assume 'yes' as answer to all prompts."),
+
+                clap::Arg::new("template")
+                    .short('t')
+                    .long("template")
+                    .value_name("name")
+                    .help("Use a template defined in the config file; templates can define hooks."),
+
+                clap::Arg::new("only")
+                    .long("only")
+                    .value_name("steps")
+                    .value_delimiter(',')
+                    .action(clap::ArgAction::Append)
+                    .conflicts_with("skip")
+                    .help("Perform only these steps (comma-separated); e.g. 'project,git'."),
+
+                clap::Arg::new("skip")
+                    .long("skip")
+                    .value_name("steps")
+                    .value_delimiter(',')
+                    .action(clap::ArgAction::Append)
+                    .help("Don't perform these steps (comma-separated); e.g. 'venv'."),
                 
                 clap::Arg::new("version")
                     .long("version")
@@ -151,48 +548,164 @@ fn create_commands() -> clap::ArgMatches {
                     .help("Show this help message and exit."),
             ]
         )
+}
+
+#[inline(always)]
+fn create_commands() -> clap::ArgMatches {
+    // `createpy <projectname>` is kept as an alias of `createpy new <projectname>`
+    project_options(
+        clap::Command::new(env!("CARGO_PKG_NAME"))
+            .version(env!("CARGO_PKG_VERSION"))
+            .propagate_version(true)
+            .arg_required_else_help(true)
+            .subcommand_negates_reqs(true)
+            .args_conflicts_with_subcommands(true)
+            .arg(clap::Arg::new("projectname").required(true))
+    )
+        .subcommand(
+            project_options(
+                clap::Command::new("new")
+                    .about("Create a new project.")
+                    .arg(clap::Arg::new("projectname").required(true))
+            )
+        )
+        .subcommand(
+            project_options(
+                clap::Command::new("init")
+                    .about("Initialize the current directory as a project, in place.")
+            )
+        )
+        .subcommand(
+            project_options(
+                clap::Command::new("add")
+                    .about("Add a component to the project in the current directory.")
+                    .arg(
+                        clap::Arg::new("component")
+                            .required(true)
+                            .value_parser(COMPONENTS)
+                    )
+            )
+                .mut_arg("license", |a| a.required_if_eq("component", "license"))
+        )
+        .subcommand(
+            clap::Command::new("add-package")
+                .about("Add a workspace package under 'packages/<name>' to the project in the current directory.")
+                .next_line_help(true)
+                .arg(clap::Arg::new("packagename").required(true))
+                .args(
+                    [
+                        clap::Arg::new("description")
+                            .short('d')
+                            .long("description")
+                            .value_name("text")
+                            .help("A short description of the package, used in the metadata and README."),
+
+                        clap::Arg::new("template")
+                            .short('t')
+                            .long("template")
+                            .value_name("name")
+                            .help("Use a template defined in the config file; templates can define hooks."),
+
+                        clap::Arg::new("noinput")
+                            .short('y')
+                            .long("yes")
+                            .action(clap::ArgAction::SetTrue)
+                            .help("Automatic yes to prompts; assume 'yes' as answer to all prompts."),
+                    ]
+                )
+        )
+        .subcommand(
+            clap::Command::new("doctor")
+                .about("Check the programs which createpy runs, and the write permission in the current directory.")
+        )
+        .subcommand(
+            clap::Command::new("templates")
+                .about("List the templates defined in the config file.")
+        )
+        .subcommand(
+            clap::Command::new("config")
+                .about("Show the config file path and content.")
+        )
         .get_matches()
 }
 
 
-pub fn parse_args() -> Arguments {
+pub fn parse_args() -> Command {
     let matches: clap::ArgMatches = create_commands();
-    let mut result = Arguments(
-        ProjectArguments(String::new()),
-        None,
-        None,
-        OtherArguments::new()
-    );
 
-    if let Some(project_name) = matches.get_one::<String>("projectname") {
-        result.0.0 = (*project_name).clone();
+    match matches.subcommand() {
+        Some(("new", sub_matches)) => Command::Project(Box::new(parse_project_args(sub_matches))),
+        Some(("init", sub_matches)) => {
+            let mut result = parse_project_args(sub_matches);
+            result.project.in_place = true;
+            Command::Project(Box::new(result))
+        }
+        Some(("add", sub_matches)) => Command::Project(Box::new(parse_add_args(sub_matches))),
+        Some(("add-package", sub_matches)) => Command::AddPackage(parse_package_args(sub_matches)),
+        Some(("doctor", _)) => Command::Doctor,
+        Some(("templates", _)) => Command::Templates,
+        Some(("config", _)) => Command::Config,
+        _ => Command::Project(Box::new(parse_project_args(&matches))),
     }
+}
 
-    if let Some(git_enabled) = matches.get_one::<bool>("gitenabled") {
-        if *git_enabled {
-            let mut cfg = GitArguments::new();
+/// Arguments of `add <component>`; only the component step is performed, in place
+fn parse_add_args(matches: &clap::ArgMatches) -> Arguments {
+    let mut result = parse_project_args(matches);
+    result.project.in_place = true;
 
-            if let Some(gitname) = matches.get_one::<String>("gitname") {
-                cfg.user_name = (*gitname).clone();
-            }
+    let component = matches
+        .get_one::<String>("component")
+        .cloned()
+        .unwrap_or_default();
 
-            if let Some(gitemail) = matches.get_one::<String>("gitemail") {
-                cfg.user_email = (*gitemail).clone();
-            }
+    if component == "git" && result.git.is_none() {
+        result.git = Some(parse_git_args(matches));
+    }
 
-            if let Some(gitremoteurl) = matches.get_one::<String>("gitremoteurl") {
-                cfg.remote_url = (*gitremoteurl).clone();
-            }
+    if component == "ci" && result.tools.ci.is_empty() {
+        result.tools.ci = String::from("auto");
+    }
 
-            if let Some(gitremotename) = matches.get_one::<String>("gitremotename") {
-                cfg.remote_name = (*gitremotename).clone();
-            }
+    result.other.only = vec![component];
+    result.other.skip.clear();
+    result
+}
 
-            if let Some(gitbranch) = matches.get_one::<String>("gitbranch") {
-                cfg.branch = (*gitbranch).clone();
-            }
+fn parse_project_args(matches: &clap::ArgMatches) -> Arguments {
+    let mut result = Arguments::new(String::new());
+
+    if let Ok(Some(project_name)) = matches.try_get_one::<String>("projectname") {
+        result.project.name = (*project_name).clone();
+    }
+
+    if let Some(description) = matches.get_one::<String>("description") {
+        result.project.description = (*description).clone();
+    }
+
+    if let Some(python) = matches.get_one::<String>("python") {
+        result.project.python = (*python).clone();
+    }
+
+    if let Some(layout) = matches.get_one::<String>("layout") {
+        result.project.layout = (*layout).clone();
+    }
+
+    if let Some(readmeformat) = matches.get_one::<String>("readmeformat") {
+        result.project.readme_format = (*readmeformat).clone();
+    }
+
+    if let Some(author) = matches.get_one::<String>("author") {
+        result.project.author = (*author).clone();
+    }
 
-            result.1 = Some(cfg);
+    if let Some(license) = matches.get_one::<String>("license") {
+        result.project.license = (*license).clone();
+    }
+
+    if let Some(git_enabled) = matches.get_one::<bool>("gitenabled") {
+        if *git_enabled {
+            result.git = Some(parse_git_args(matches));
         }
     }
 
@@ -204,16 +717,152 @@ pub fn parse_args() -> Arguments {
                 cfg.path = (*venvpath).clone();
             }
 
+            if let Some(venvmanager) = matches.get_one::<String>("venvmanager") {
+                cfg.manager = (*venvmanager).clone();
+            }
+
             if let Some(venvscript) = matches.get_one::<String>("venvscript") {
                 cfg.script = (*venvscript).clone();
             }
 
-            result.2 = Some(cfg);
+            if let Some(without_pip) = matches.get_one::<bool>("venvwithoutpip") {
+                cfg.without_pip = *without_pip;
+            }
+
+            result.venv = Some(cfg);
         }
     }
 
+    if let Some(tests) = matches.get_one::<String>("tests") {
+        result.tools.tests = (*tests).clone();
+    }
+
+    if let Some(lint) = matches.get_one::<String>("lint") {
+        result.tools.lint = (*lint).clone();
+    }
+
+    if let Some(typecheck) = matches.get_one::<String>("typecheck") {
+        result.tools.typecheck = (*typecheck).clone();
+    }
+
+    if let Some(strictness) = matches.get_one::<String>("strictness") {
+        result.tools.strictness = (*strictness).clone();
+    }
+
+    if let Some(precommit) = matches.get_one::<bool>("precommit") {
+        result.tools.pre_commit = *precommit;
+    }
+
+    if let Some(ci) = matches.get_one::<String>("ci") {
+        result.tools.ci = (*ci).clone();
+    }
+
+    if let Some(cipython) = matches.get_many::<String>("cipython") {
+        result.tools.ci_python = cipython.cloned().collect();
+    }
+
+    if let Some(docker) = matches.get_one::<String>("docker") {
+        result.tools.docker = (*docker).clone();
+    }
+
+    if let Some(compose) = matches.get_one::<bool>("compose") {
+        result.tools.compose = *compose;
+    }
+
+    if let Some(lock) = matches.get_one::<String>("lock") {
+        result.tools.lock = (*lock).clone();
+    }
+
+    if let Some(noinput) = matches.get_one::<bool>("noinput") {
+        result.other.reinitialize_without_input = *noinput;
+    }
+
+    if let Some(template) = matches.get_one::<String>("template") {
+        result.other.template = (*template).clone();
+    }
+
+    if let Some(only) = matches.get_many::<String>("only") {
+        result.other.only = only.cloned().collect();
+    }
+
+    if let Some(skip) = matches.get_many::<String>("skip") {
+        result.other.skip = skip.cloned().collect();
+    }
+
+    result
+}
+
+fn parse_git_args(matches: &clap::ArgMatches) -> GitArguments {
+    let mut cfg = GitArguments::new();
+
+    if let Some(gitname) = matches.get_one::<String>("gitname") {
+        cfg.user_name = (*gitname).clone();
+    }
+
+    if let Some(gitemail) = matches.get_one::<String>("gitemail") {
+        cfg.user_email = (*gitemail).clone();
+    }
+
+    if let Some(gitremoteurl) = matches.get_one::<String>("gitremoteurl") {
+        cfg.remote_url = (*gitremoteurl).clone();
+    }
+
+    if let Some(gitremotename) = matches.get_one::<String>("gitremotename") {
+        cfg.remote_name = (*gitremotename).clone();
+    }
+
+    if let Some(gitbranch) = matches.get_one::<String>("gitbranch") {
+        cfg.branch = (*gitbranch).clone();
+    }
+
+    if let Some(gitremote) = matches.get_many::<String>("gitremote") {
+        cfg.remotes = gitremote.cloned().collect();
+    }
+
+    if let Some(gitpushurl) = matches.get_many::<String>("gitpushurl") {
+        cfg.push_urls = gitpushurl.cloned().collect();
+    }
+
+    if let Some(gitremotestyle) = matches.get_one::<String>("gitremotestyle") {
+        cfg.remote_style = (*gitremotestyle).clone();
+    }
+
+    if let Some(gitconfig) = matches.get_many::<String>("gitconfig") {
+        cfg.config = gitconfig.cloned().collect();
+    }
+
+    if let Some(gitattributes) = matches.get_one::<bool>("gitattributes") {
+        cfg.attributes = *gitattributes;
+    }
+
+    if let Some(gitsubmodule) = matches.get_many::<String>("gitsubmodule") {
+        cfg.submodules = gitsubmodule.cloned().collect();
+    }
+
+    if let Some(gitcommit) = matches.get_one::<bool>("gitcommit") {
+        cfg.commit = *gitcommit;
+    }
+
+    cfg
+}
+
+fn parse_package_args(matches: &clap::ArgMatches) -> PackageArguments {
+    let mut result = PackageArguments::new(String::new());
+
+    if let Some(package_name) = matches.get_one::<String>("packagename") {
+        result.name = (*package_name).clone();
+    }
+
+    if let Some(description) = matches.get_one::<String>("description") {
+        result.description = (*description).clone();
+    }
+
+    if let Some(template) = matches.get_one::<String>("template") {
+        result.template = (*template).clone();
+    }
+
     if let Some(noinput) = matches.get_one::<bool>("noinput") {
-        result.3.reinitialize_without_input = *noinput;
+        result.reinitialize_without_input = *noinput;
     }
 
     result
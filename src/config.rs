@@ -0,0 +1,425 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::arguments;
+
+/// Hook commands; they are run by the system shell
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Commands which are run before creating anything, in the project base directory
+    pub pre_create: Vec<String>,
+
+    /// Commands which are run after all steps, in the project directory
+    pub post_create: Vec<String>,
+}
+
+/// A named preset, selected with `--template <name>`
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TemplateConfig {
+    pub hooks: HooksConfig,
+}
+
+/// URL patterns of a remote shorthand; `{owner}`, `{repo}` and `{project_name}` are replaced
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RemotePattern {
+    pub ssh: String,
+    pub https: String,
+}
+
+/// Remote shorthands, e.g. `gh:owner/repo`; `gh` (GitHub) and `gl` (GitLab) are built in,
+/// and can be overridden
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct RemotesConfig {
+    /// URL style, `ssh` (default) or `https`
+    pub style: String,
+
+    /// Shorthand of `owner/repo` remotes, which have no prefix; defaults to `gh`
+    pub default: String,
+
+    #[serde(flatten)]
+    pub patterns: BTreeMap<String, RemotePattern>,
+}
+
+impl RemotesConfig {
+    /// Returns the patterns of `prefix` shorthand
+    fn pattern(&self, prefix: &str) -> Option<RemotePattern> {
+        if let Some(pattern) = self.patterns.get(prefix) {
+            return Some(pattern.clone());
+        }
+
+        let host = match prefix {
+            "gh" => "github.com",
+            "gl" => "gitlab.com",
+            _ => return None,
+        };
+
+        Some(RemotePattern {
+            ssh: format!("git@{}:{{owner}}/{{repo}}.git", host),
+            https: format!("https://{}/{{owner}}/{{repo}}.git", host),
+        })
+    }
+
+    /// Expand `url` if it's a shorthand, `<prefix>:<owner>[/<repo>]` or `<owner>/<repo>`;
+    /// `repo` defaults to `project_name`. Other URLs are returned as they are.
+    pub fn expand(&self, url: &str, style: &str, project_name: &str) -> Result<String, String> {
+        let default = if self.default.is_empty() {
+            "gh"
+        } else {
+            self.default.as_str()
+        };
+
+        let (prefix, path) = match url.split_once(':') {
+            Some((prefix, path)) if !path.starts_with("//") && self.pattern(prefix).is_some() => {
+                (prefix, path)
+            }
+            // `owner/repo`, unless it's a local path
+            None if url.matches('/').count() == 1
+                && !url.starts_with(['.', '/'])
+                && !url.ends_with('/')
+                && !std::path::Path::new(url).exists() =>
+            {
+                (default, url)
+            }
+            _ => return Ok(String::from(url)),
+        };
+
+        let Some(pattern) = self.pattern(prefix) else {
+            return Err(format!("unknown remote shorthand: '{}'", prefix));
+        };
+
+        let (owner, repo) = path.split_once('/').unwrap_or((path, project_name));
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+
+        if owner.is_empty() || repo.is_empty() {
+            return Err(format!(
+                "invalid remote shorthand: '{}'; expected {}:<owner>[/<repo>]",
+                url, prefix
+            ));
+        }
+
+        let style = match (style, self.style.as_str()) {
+            ("", "") => "ssh",
+            ("", style) => style,
+            (style, _) => style,
+        };
+
+        let text = match style {
+            "ssh" => &pattern.ssh,
+            "https" => &pattern.https,
+            other => {
+                return Err(format!(
+                    "not supported remote style: '{}'; use 'ssh' or 'https'",
+                    other
+                ));
+            }
+        };
+
+        if text.is_empty() {
+            return Err(format!(
+                "remote shorthand '{}' has no {} pattern",
+                prefix, style
+            ));
+        }
+
+        Ok(text
+            .replace("{owner}", owner)
+            .replace("{repo}", repo)
+            .replace("{project_name}", project_name))
+    }
+}
+
+/// User configuration, loaded from `config.toml`; see [`Config::path`]
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub hooks: HooksConfig,
+    pub templates: BTreeMap<String, TemplateConfig>,
+    pub remotes: RemotesConfig,
+}
+
+impl Config {
+    /// Configuration file path; `$CREATEPY_CONFIG` if it's set, otherwise
+    /// `createpy/config.toml` in the user configuration directory.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("CREATEPY_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+
+        let directory = if cfg!(windows) {
+            std::env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+
+        directory.map(|d| d.join("createpy").join("config.toml"))
+    }
+
+    /// Load configuration from [`Config::path`]; a missing file means an empty configuration
+    pub fn load() -> Result<Config, String> {
+        match Config::path() {
+            Some(path) if path.is_file() => Config::from_file(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    pub fn from_file(path: &std::path::Path) -> Result<Config, String> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(o) => o,
+            Err(e) => {
+                return Err(format!("cannot read {}: {}", path.display(), e));
+            }
+        };
+
+        toml::from_str(&content).map_err(|e| format!("invalid config file {}: {}", path.display(), e))
+    }
+
+    /// Add configured hooks, and the selected template hooks, to `args`; and expand remote
+    /// shorthands
+    pub fn apply(&self, args: &mut arguments::Arguments) -> Result<(), String> {
+        self.expand_remotes(args)?;
+
+        self.apply_hooks(&args.other.template, &mut args.hooks)
+    }
+
+    /// Add configured hooks, and the selected template hooks, to the arguments of
+    /// `add-package`
+    pub fn apply_package(&self, args: &mut arguments::PackageArguments) -> Result<(), String> {
+        self.apply_hooks(&args.template, &mut args.hooks)
+    }
+
+    /// Add configured hooks, and the hooks of `template` (if it's not empty), before `hooks`
+    fn apply_hooks(
+        &self,
+        template: &str,
+        hooks: &mut arguments::HooksArguments,
+    ) -> Result<(), String> {
+        let mut configured = vec![&self.hooks];
+
+        if !template.is_empty() {
+            match self.templates.get(template) {
+                Some(t) => configured.push(&t.hooks),
+                None => {
+                    return Err(format!("no such template: '{}'", template));
+                }
+            }
+        }
+
+        let mut pre_create: Vec<String> = Vec::new();
+        let mut post_create: Vec<String> = Vec::new();
+
+        for h in configured {
+            pre_create.extend(h.pre_create.iter().cloned());
+            post_create.extend(h.post_create.iter().cloned());
+        }
+
+        // hooks which are given directly are run last
+        pre_create.append(&mut hooks.pre_create);
+        post_create.append(&mut hooks.post_create);
+
+        hooks.pre_create = pre_create;
+        hooks.post_create = post_create;
+
+        Ok(())
+    }
+
+    /// Expand remote shorthands of the git arguments; see [`RemotesConfig::expand`]
+    fn expand_remotes(&self, args: &mut arguments::Arguments) -> Result<(), String> {
+        let Some(git) = args.git.as_mut() else {
+            return Ok(());
+        };
+
+        // the repository is named after the project directory
+        let directory = if args.project.name.is_empty() {
+            std::env::current_dir().unwrap_or_default()
+        } else {
+            PathBuf::from(&args.project.name)
+        };
+
+        let project_name = directory
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        if !git.remote_url.is_empty() {
+            git.remote_url =
+                self.remotes
+                    .expand(&git.remote_url, &git.remote_style, &project_name)?;
+        }
+
+        for entry in git
+            .remotes
+            .iter_mut()
+            .chain(git.push_urls.iter_mut())
+            .chain(git.submodules.iter_mut())
+        {
+            if let Some((name, url)) = entry.split_once('=') {
+                let url = self
+                    .remotes
+                    .expand(url.trim(), &git.remote_style, &project_name)?;
+                *entry = format!("{}={}", name, url);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remotes(text: &str) -> RemotesConfig {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn expand_builtin_shorthands() {
+        let r = RemotesConfig::default();
+
+        assert_eq!(
+            r.expand("gh:owner/repo", "", "project"),
+            Ok(String::from("git@github.com:owner/repo.git"))
+        );
+        assert_eq!(
+            r.expand("gl:group/repo.git", "", "project"),
+            Ok(String::from("git@gitlab.com:group/repo.git"))
+        );
+    }
+
+    #[test]
+    fn expand_custom_prefixes() {
+        let r = remotes(
+            r#"
+            [corp]
+            ssh = "git@git.corp.example:{owner}/{repo}.git"
+            https = "https://git.corp.example/{owner}/{repo}.git"
+
+            [gh]
+            ssh = "git@github.example:{owner}/{repo}.git"
+            "#,
+        );
+
+        assert_eq!(
+            r.expand("corp:team/app", "", "project"),
+            Ok(String::from("git@git.corp.example:team/app.git"))
+        );
+        // built-in shorthands can be overridden
+        assert_eq!(
+            r.expand("gh:owner/repo", "", "project"),
+            Ok(String::from("git@github.example:owner/repo.git"))
+        );
+    }
+
+    #[test]
+    fn expand_owner_repo() {
+        assert_eq!(
+            RemotesConfig::default().expand("owner/repo", "", "project"),
+            Ok(String::from("git@github.com:owner/repo.git"))
+        );
+
+        let r = remotes(
+            r#"
+            default = "corp"
+
+            [corp]
+            ssh = "git@git.corp.example:{owner}/{repo}.git"
+            "#,
+        );
+
+        assert_eq!(
+            r.expand("team/app", "", "project"),
+            Ok(String::from("git@git.corp.example:team/app.git"))
+        );
+    }
+
+    #[test]
+    fn expand_keeps_local_paths() {
+        let r = RemotesConfig::default();
+
+        for url in ["../repo", "./repo", "/srv/repo", "repo/", "src/config.rs"] {
+            assert_eq!(r.expand(url, "", "project"), Ok(String::from(url)));
+        }
+    }
+
+    #[test]
+    fn expand_keeps_urls() {
+        let r = RemotesConfig::default();
+
+        for url in [
+            "https://github.com/owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+            "file:///srv/repo.git",
+            "gh://owner/repo",
+            "git@example.com:owner/repo.git",
+        ] {
+            assert_eq!(r.expand(url, "", "project"), Ok(String::from(url)));
+        }
+    }
+
+    #[test]
+    fn expand_repo_defaults_to_project_name() {
+        assert_eq!(
+            RemotesConfig::default().expand("gh:owner", "", "project"),
+            Ok(String::from("git@github.com:owner/project.git"))
+        );
+
+        let r = remotes(
+            r#"
+            [corp]
+            ssh = "git@git.corp.example:{owner}/{project_name}-{repo}.git"
+            "#,
+        );
+
+        assert_eq!(
+            r.expand("corp:team/app", "", "project"),
+            Ok(String::from("git@git.corp.example:team/project-app.git"))
+        );
+    }
+
+    #[test]
+    fn expand_style() {
+        let r = RemotesConfig::default();
+
+        assert_eq!(
+            r.expand("gh:owner/repo", "https", "project"),
+            Ok(String::from("https://github.com/owner/repo.git"))
+        );
+
+        // `--remote-style` overrides the configured style
+        let r = remotes(r#"style = "https""#);
+
+        assert_eq!(
+            r.expand("gh:owner/repo", "", "project"),
+            Ok(String::from("https://github.com/owner/repo.git"))
+        );
+        assert_eq!(
+            r.expand("gh:owner/repo", "ssh", "project"),
+            Ok(String::from("git@github.com:owner/repo.git"))
+        );
+        assert!(r.expand("gh:owner/repo", "git", "project").is_err());
+    }
+
+    #[test]
+    fn expand_errors() {
+        let r = remotes(
+            r#"
+            [corp]
+            ssh = "git@git.corp.example:{owner}/{repo}.git"
+            "#,
+        );
+
+        assert_eq!(
+            r.expand("corp:team/app", "https", "project"),
+            Err(String::from("remote shorthand 'corp' has no https pattern"))
+        );
+        assert!(r.expand("gh:/repo", "", "project").is_err());
+        assert!(r.expand("gh:owner/", "", "project").is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::arguments;
+use crate::makers;
+
+/// Programs which the makers may run
+const PROGRAMS: [&str; 8] = [
+    "git",
+    "python3",
+    "virtualenv",
+    "uv",
+    "conda",
+    "poetry",
+    "pdm",
+    "hatch",
+];
+
+/// The first git version which supports `git init -b <branch>`
+pub const GIT_INIT_BRANCH: (u32, u32, u32) = (2, 28, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Ok,
+
+    /// Some steps may fail, e.g. an optional program is missing
+    Warning,
+
+    /// The default steps will fail
+    Error,
+}
+
+/// Result of one check
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub name: String,
+    pub level: Level,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(name: &str, level: Level, message: String) -> Diagnostic {
+        Diagnostic {
+            name: String::from(name),
+            level,
+            message,
+        }
+    }
+
+    fn print(&self) {
+        match self.level {
+            Level::Ok => println!("│   {} {}: {}", "ok".green(), self.name, self.message),
+            Level::Warning => println!(
+                "│   {} {}: {}",
+                "warning:".yellow(),
+                self.name,
+                self.message
+            ),
+            Level::Error => println!(
+                "│   {} {}: {}",
+                "error:".red().bold(),
+                self.name,
+                self.message
+            ),
+        }
+    }
+}
+
+/// Returns the first line of `<program> --version`, or `None` if it cannot be run
+pub fn version(program: &str) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .arg("--version")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    // some programs (e.g. old pythons) print their version to stderr
+    let text = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+
+    String::from_utf8_lossy(&text)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+}
+
+/// Returns the installed git version, e.g. `(2, 39, 5)` for `git version 2.39.5`
+pub fn git_version() -> Option<(u32, u32, u32)> {
+    let text = version("git")?;
+    let mut numbers = text
+        .split_whitespace()
+        .nth(2)?
+        .split('.')
+        .map(|n| n.parse::<u32>().ok());
+
+    let major = numbers.next().flatten()?;
+    let minor = numbers.next().flatten().unwrap_or(0);
+    let patch = numbers.next().flatten().unwrap_or(0);
+
+    Some((major, minor, patch))
+}
+
+/// Check that `program` can be run; `level` is used if it cannot
+fn program(name: &str, level: Level) -> Diagnostic {
+    match version(name) {
+        Some(v) => Diagnostic::new(name, Level::Ok, v),
+        None => Diagnostic::new(name, level, String::from("not found")),
+    }
+}
+
+/// Check that `python -m venv` works, by creating a virtual environment in a temporary
+/// directory; Debian splits `ensurepip` into the `python3-venv` package, and `venv` fails
+/// without it
+fn venv_module(python: &str) -> Diagnostic {
+    let name = format!("{} -m venv", python);
+    let probe = std::env::temp_dir().join(format!("createpy-venv-{}", std::process::id()));
+
+    let output = std::process::Command::new(python)
+        .args(["-m", "venv", "--without-pip"])
+        .arg(&probe)
+        .stdin(std::process::Stdio::null())
+        .output();
+
+    let _ = std::fs::remove_dir_all(&probe);
+
+    match output {
+        Ok(o) if o.status.success() => {}
+        Ok(o) => {
+            let stderr = String::from_utf8_lossy(&o.stderr);
+            let reason = stderr
+                .lines()
+                .rfind(|l| !l.trim().is_empty())
+                .unwrap_or("failed");
+
+            return Diagnostic::new(&name, Level::Error, String::from(reason.trim()));
+        }
+        Err(_) => {
+            return Diagnostic::new(&name, Level::Error, format!("{} is not found", python));
+        }
+    }
+
+    // pip is installed by `ensurepip`
+    let ensurepip = std::process::Command::new(python)
+        .args(["-c", "import ensurepip"])
+        .stdin(std::process::Stdio::null())
+        .output();
+
+    match ensurepip {
+        Ok(o) if o.status.success() => Diagnostic::new(&name, Level::Ok, String::from("works")),
+        _ => Diagnostic::new(
+            &name,
+            Level::Error,
+            String::from("ensurepip is not available; e.g. install the 'python3-venv' package"),
+        ),
+    }
+}
+
+/// Check that git supports `git init -b`; older versions work, with `git symbolic-ref`
+fn git_init_branch() -> Diagnostic {
+    let name = "git init -b";
+
+    match git_version() {
+        Some(v) if v >= GIT_INIT_BRANCH => {
+            Diagnostic::new(name, Level::Ok, String::from("supported"))
+        }
+        Some((major, minor, patch)) => Diagnostic::new(
+            name,
+            Level::Warning,
+            format!(
+                "git {}.{}.{} is older than {}.{}; 'git symbolic-ref' is used instead",
+                major, minor, patch, GIT_INIT_BRANCH.0, GIT_INIT_BRANCH.1
+            ),
+        ),
+        None => Diagnostic::new(name, Level::Warning, String::from("git is not found")),
+    }
+}
+
+/// Check that files can be created in `directory`
+fn writable(directory: &Path) -> Diagnostic {
+    let name = format!("write permission in {}", directory.display());
+    let probe = directory.join(format!(".createpy-{}", std::process::id()));
+
+    match std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+    {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            Diagnostic::new(&name, Level::Ok, String::from("yes"))
+        }
+        Err(e) => Diagnostic::new(&name, Level::Error, e.to_string()),
+    }
+}
+
+/// Check the whole environment; `base` is the directory where projects are created
+pub fn diagnose(base: &Path) -> Vec<Diagnostic> {
+    let mut result: Vec<Diagnostic> = PROGRAMS
+        .iter()
+        .map(|name| {
+            if *name == "python3" {
+                program(name, Level::Error)
+            } else {
+                program(name, Level::Warning)
+            }
+        })
+        .collect();
+
+    result.push(venv_module("python3"));
+    result.push(git_init_branch());
+    result.push(writable(base));
+    result
+}
+
+/// Print diagnostics of the environment; returns an error if the default steps will fail
+pub fn run(base: &Path) -> Result<(), String> {
+    println!("{}", "Checking environment ...".bold());
+
+    let diagnostics = diagnose(base);
+
+    for diagnostic in &diagnostics {
+        diagnostic.print();
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.level == Level::Error)
+        .count();
+
+    if errors == 0 {
+        println!("└── {}", "END\n".green().bold());
+        Ok(())
+    } else {
+        let e = format!("{} check(s) failed", errors);
+        println!("└── {} {}", "error:".red().bold(), e);
+        Err(e)
+    }
+}
+
+/// Check the requirements of the selected steps, before performing any of them; e.g.
+/// `virtualenv` is required only if the venv step uses it
+pub fn preflight(args: &arguments::Arguments) -> Result<(), String> {
+    let selected = |step: &str| {
+        (args.other.only.is_empty() || args.other.only.iter().any(|s| s == step))
+            && !args.other.skip.iter().any(|s| s == step)
+    };
+
+    let mut checks: Vec<Diagnostic> = Vec::new();
+
+    if selected("project") {
+        let path = PathBuf::from(&args.project.name);
+
+        // new projects are created in the parent directory
+        let directory = if args.project.in_place {
+            if args.project.name.is_empty() {
+                PathBuf::from(".")
+            } else {
+                path
+            }
+        } else {
+            match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            }
+        };
+
+        if directory.is_dir() {
+            checks.push(writable(&directory));
+        }
+    }
+
+    // in place, an existing git repository and virtual environment are kept; their makers
+    // skip creating them, the same lookup is used here
+    let existing = args.project.in_place.then(|| {
+        if args.project.name.is_empty() {
+            PathBuf::from(".")
+        } else {
+            PathBuf::from(&args.project.name)
+        }
+    });
+
+    let has_git = existing.as_ref().is_some_and(|d| d.join(".git").exists());
+    let has_venv = existing
+        .as_ref()
+        .is_some_and(|d| makers::find_venv(d).is_some());
+
+    if let Some(git) = &args.git {
+        let git_step = selected("git") && !has_git;
+        let commit_step = git.commit && selected("commit");
+
+        // the native backend doesn't run the `git` program, except for adding submodules
+        let shell_git = !cfg!(feature = "native-git") || (git_step && !git.submodules.is_empty());
+
+        if shell_git && (git_step || commit_step) {
+            checks.push(program("git", Level::Error));
+        }
+    }
+
+    // poetry is asked for its environment, even if it exists
+    let venv = args
+        .venv
+        .as_ref()
+        .filter(|v| selected("venv") && !(has_venv && v.manager != "poetry"));
+
+    if let Some(venv) = venv {
+        let python = if args.project.python.is_empty() {
+            String::from("python3")
+        } else {
+            format!("python{}", args.project.python)
+        };
+
+        match venv.manager.as_str() {
+            "" | "pip" if venv.script == "virtualenv" => {
+                checks.push(program("virtualenv", Level::Error));
+            }
+            // only `ensurepip` of the interpreter is used
+            "" | "pip" if venv.script == "native" && venv.without_pip => {
+                checks.push(program(&python, Level::Error));
+            }
+            "" | "pip" => checks.push(venv_module(&python)),
+            manager => checks.push(program(manager, Level::Error)),
+        }
+    }
+
+    if args.tools.lock == "uv" && selected("lock") {
+        checks.push(program("uv", Level::Error));
+    }
+
+    let failed: Vec<String> = checks
+        .iter()
+        .filter(|d| d.level == Level::Error)
+        .map(|d| format!("{}: {}", d.name, d.message))
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("preflight check failed; {}", failed.join("; ")))
+    }
+}
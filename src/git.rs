@@ -0,0 +1,510 @@
+use colored::Colorize;
+use std::path::Path;
+
+use crate::doctor;
+use crate::makers::{command_output, run_command};
+
+/// Git operations of the git and commit steps
+pub trait GitBackend: std::fmt::Debug {
+    /// Create an empty repository in `path`, whose HEAD points to `branch`
+    fn init(&self, path: &Path, branch: &str) -> Result<(), String>;
+
+    /// Set `key` (e.g. `user.name`) in the repository configuration
+    fn config(&self, path: &Path, key: &str, value: &str) -> Result<(), String>;
+
+    fn remote_add(&self, path: &Path, name: &str, url: &str) -> Result<(), String>;
+
+    /// Stage all files, except the paths which match `exclude`; patterns are paths
+    /// relative to the repository, or `**/<name>/**` for directories in any depth
+    fn add_all(&self, path: &Path, exclude: &[String]) -> Result<(), String>;
+
+    /// Commit the staged files
+    fn commit(&self, path: &Path, message: &str) -> Result<(), String>;
+
+    /// Returns `true` if HEAD points to a commit
+    fn has_commits(&self, path: &Path) -> bool;
+
+    /// Fetch URLs of the remotes of the repository in `path`; empty if it isn't a repository
+    fn remote_urls(&self, path: &Path) -> Vec<String>;
+
+    /// Clone `url` into `subpath`, and register it as a submodule; returns the abbreviated
+    /// commit id which is checked out
+    fn submodule_add(&self, path: &Path, subpath: &str, url: &str) -> Result<String, String>;
+}
+
+/// Returns the backend which is used by the makers; the native one, if the `native-git`
+/// feature is enabled
+pub fn backend() -> Box<dyn GitBackend> {
+    #[cfg(feature = "native-git")]
+    return Box::new(NativeGit);
+
+    #[cfg(not(feature = "native-git"))]
+    return Box::new(ShellGit);
+}
+
+/// Quote `arg` for printing, if it contains whitespaces
+fn quote(arg: &str) -> String {
+    if arg.contains(char::is_whitespace) {
+        format!("{:?}", arg)
+    } else {
+        String::from(arg)
+    }
+}
+
+/// Runs the `git` program
+#[derive(Debug, Default)]
+pub struct ShellGit;
+
+impl ShellGit {
+    /// perform `git <args>` command in `path`
+    fn git(&self, path: &Path, args: &[&str]) -> Result<(), String> {
+        let display: Vec<String> = args.iter().map(|arg| quote(arg)).collect();
+
+        println!("│   {} - git {}", "Executing".purple(), display.join(" "));
+
+        self.run(path, args)
+    }
+
+    /// perform `git <args>` command in `path`, without printing it
+    fn run(&self, path: &Path, args: &[&str]) -> Result<(), String> {
+        let mut command = std::process::Command::new("git");
+        command.args(args);
+        command.current_dir(path);
+
+        run_command(command, "git")
+    }
+}
+
+impl GitBackend for ShellGit {
+    fn init(&self, path: &Path, branch: &str) -> Result<(), String> {
+        // `git init -b` is supported since git 2.28
+        if doctor::git_version().is_some_and(|v| v < doctor::GIT_INIT_BRANCH) {
+            self.git(path, &["init"])?;
+            return self.git(
+                path,
+                &["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)],
+            );
+        }
+
+        self.git(path, &["init", "-b", branch])
+    }
+
+    fn config(&self, path: &Path, key: &str, value: &str) -> Result<(), String> {
+        self.git(path, &["config", key, value])
+    }
+
+    fn remote_add(&self, path: &Path, name: &str, url: &str) -> Result<(), String> {
+        self.git(path, &["remote", "add", name, url])
+    }
+
+    fn add_all(&self, path: &Path, exclude: &[String]) -> Result<(), String> {
+        let pathspecs: Vec<String> = exclude
+            .iter()
+            .map(|pattern| format!(":(exclude,glob){}", pattern))
+            .collect();
+
+        println!("│   {} - git add -A", "Executing".purple());
+
+        let mut args = vec!["add", "-A", "--", "."];
+        args.extend(pathspecs.iter().map(String::as_str));
+        self.run(path, &args)
+    }
+
+    fn commit(&self, path: &Path, message: &str) -> Result<(), String> {
+        self.git(path, &["commit", "--quiet", "-m", message])
+    }
+
+    fn submodule_add(&self, path: &Path, subpath: &str, url: &str) -> Result<String, String> {
+        // since git 2.38.1 local repositories cannot be cloned as submodules by default; a
+        // path or `file://` URL is given explicitly, so it's allowed for this command
+        let local = url.starts_with("file://") || !url.contains(':');
+
+        let mut args = Vec::new();
+        if local {
+            args.extend(["-c", "protocol.file.allow=always"]);
+        }
+        args.extend(["submodule", "add", "--quiet", "--", url, subpath]);
+        self.git(path, &args)?;
+
+        let mut command = std::process::Command::new("git");
+        command.args(["rev-parse", "--short", "HEAD"]);
+        command.current_dir(path.join(subpath));
+
+        command_output(command, "git").map(|id| String::from(id.trim()))
+    }
+
+    fn has_commits(&self, path: &Path) -> bool {
+        std::process::Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+            .current_dir(path)
+            .stdout(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+
+    fn remote_urls(&self, path: &Path) -> Vec<String> {
+        let mut command = std::process::Command::new("git");
+        command.args(["config", "--get-regexp", r"^remote\..*\.url$"]);
+        command.current_dir(path);
+
+        // git fails if there's no remote
+        command_output(command, "git")
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| line.split_once(' ').map(|(_, url)| String::from(url)))
+            .collect()
+    }
+}
+
+/// Implements git operations with `gix`, without the `git` program
+#[cfg(feature = "native-git")]
+#[derive(Debug, Default)]
+pub struct NativeGit;
+
+#[cfg(feature = "native-git")]
+impl NativeGit {
+    fn open(path: &Path) -> Result<gix::Repository, String> {
+        gix::open(path).map_err(|e| e.to_string())
+    }
+
+    /// Set `key` in the configuration file of the repository; `key` is
+    /// `<section>[.<subsection>].<name>`
+    fn set(path: &Path, key: &str, value: &str) -> Result<(), String> {
+        let (section, rest) = key
+            .split_once('.')
+            .ok_or_else(|| format!("invalid git config key: {}", key))?;
+
+        let (subsection, name) = match rest.rsplit_once('.') {
+            Some((subsection, name)) => (Some(subsection), name),
+            None => (None, rest),
+        };
+
+        let file_path = NativeGit::open(path)?.git_dir().join("config");
+
+        let mut file =
+            gix::config::File::from_path_no_includes(file_path.clone(), gix::config::Source::Local)
+                .map_err(|e| e.to_string())?;
+
+        file.set_raw_value_by(section, subsection.map(Into::into), name.to_owned(), value)
+            .map_err(|e| e.to_string())?;
+
+        let mut out = std::fs::File::create(&file_path)
+            .map_err(|e| format!("cannot write {}: {}", file_path.display(), e))?;
+
+        file.write_to(&mut out).map_err(|e| e.to_string())
+    }
+
+    /// Returns `true` if `relative` path (with `/` separators) matches one of `exclude`
+    fn excluded(relative: &str, exclude: &[String]) -> bool {
+        exclude.iter().any(|pattern| {
+            match pattern
+                .strip_prefix("**/")
+                .and_then(|p| p.strip_suffix("/**"))
+            {
+                Some(name) => relative.split('/').any(|c| c == name),
+                None => relative == pattern || relative.starts_with(&format!("{}/", pattern)),
+            }
+        })
+    }
+
+    /// Write blobs of the files in `directory`, and add them to `editor`; files which are
+    /// ignored by `.gitignore` are skipped, like by `git add`
+    fn add_directory(
+        repo: &gix::Repository,
+        editor: &mut gix::object::tree::Editor<'_>,
+        ignore: &mut gix::AttributeStack<'_>,
+        root: &Path,
+        directory: &Path,
+        exclude: &[String],
+    ) -> Result<(), String> {
+        let entries = std::fs::read_dir(directory)
+            .map_err(|e| format!("cannot read {}: {}", directory.display(), e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path();
+
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| e.to_string())?
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            if relative == ".git" || NativeGit::excluded(&relative, exclude) {
+                continue;
+            }
+
+            let file_type = entry.file_type().map_err(|e| e.to_string())?;
+
+            let mode = file_type.is_dir().then_some(gix::index::entry::Mode::DIR);
+            let ignored = ignore
+                .at_path(&relative, mode)
+                .map_err(|e| e.to_string())?
+                .is_excluded();
+
+            if ignored {
+                continue;
+            }
+
+            // submodules are added as links to their HEAD commit
+            if file_type.is_dir() && path.join(".git").exists() {
+                let id = gix::open(&path)
+                    .map_err(|e| e.to_string())?
+                    .head_id()
+                    .map_err(|e| format!("submodule {} has no commit: {}", relative, e))?
+                    .detach();
+
+                editor
+                    .upsert(relative.as_str(), gix::object::tree::EntryKind::Commit, id)
+                    .map_err(|e| e.to_string())?;
+                continue;
+            }
+
+            let (kind, data) = if file_type.is_dir() {
+                NativeGit::add_directory(repo, editor, ignore, root, &path, exclude)?;
+                continue;
+            } else if file_type.is_symlink() {
+                let target = std::fs::read_link(&path).map_err(|e| e.to_string())?;
+                (
+                    gix::object::tree::EntryKind::Link,
+                    target.to_string_lossy().into_owned().into_bytes(),
+                )
+            } else {
+                let data = std::fs::read(&path)
+                    .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+                (NativeGit::blob_kind(&entry), data)
+            };
+
+            let id = repo.write_blob(&data).map_err(|e| e.to_string())?;
+            editor
+                .upsert(relative.as_str(), kind, id)
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn blob_kind(entry: &std::fs::DirEntry) -> gix::object::tree::EntryKind {
+        use std::os::unix::fs::PermissionsExt;
+
+        match entry.metadata() {
+            Ok(m) if m.permissions().mode() & 0o111 != 0 => {
+                gix::object::tree::EntryKind::BlobExecutable
+            }
+            _ => gix::object::tree::EntryKind::Blob,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn blob_kind(_entry: &std::fs::DirEntry) -> gix::object::tree::EntryKind {
+        gix::object::tree::EntryKind::Blob
+    }
+}
+
+#[cfg(feature = "native-git")]
+impl GitBackend for NativeGit {
+    fn init(&self, path: &Path, branch: &str) -> Result<(), String> {
+        println!(
+            "│   {} - git init -b {} (native)",
+            "Executing".purple(),
+            branch
+        );
+
+        let repo = gix::init(path).map_err(|e| e.to_string())?;
+
+        std::fs::write(
+            repo.git_dir().join("HEAD"),
+            format!("ref: refs/heads/{}\n", branch),
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    fn config(&self, path: &Path, key: &str, value: &str) -> Result<(), String> {
+        println!(
+            "│   {} - git config {} {} (native)",
+            "Executing".purple(),
+            key,
+            quote(value)
+        );
+
+        NativeGit::set(path, key, value)
+    }
+
+    fn remote_add(&self, path: &Path, name: &str, url: &str) -> Result<(), String> {
+        println!(
+            "│   {} - git remote add {} {} (native)",
+            "Executing".purple(),
+            name,
+            url
+        );
+
+        let repo = NativeGit::open(path)?;
+
+        if repo.remote_names().iter().any(|n| n.as_ref() == name) {
+            return Err(format!("remote {} already exists", name));
+        }
+
+        NativeGit::set(path, &format!("remote.{}.url", name), url)?;
+        NativeGit::set(
+            path,
+            &format!("remote.{}.fetch", name),
+            &format!("+refs/heads/*:refs/remotes/{}/*", name),
+        )
+    }
+
+    fn add_all(&self, path: &Path, exclude: &[String]) -> Result<(), String> {
+        println!("│   {} - git add -A (native)", "Executing".purple());
+
+        let repo = NativeGit::open(path)?;
+        let root = repo
+            .workdir()
+            .ok_or_else(|| String::from("repository has no worktree"))?
+            .to_path_buf();
+
+        let mut editor = repo
+            .edit_tree(gix::ObjectId::empty_tree(repo.object_hash()))
+            .map_err(|e| e.to_string())?;
+
+        let state = gix::index::State::new(repo.object_hash());
+        let mut ignore = repo
+            .excludes(&state, None, Default::default())
+            .map_err(|e| e.to_string())?;
+
+        NativeGit::add_directory(&repo, &mut editor, &mut ignore, &root, &root, exclude)?;
+
+        let tree = editor.write().map_err(|e| e.to_string())?;
+
+        let mut index = repo.index_from_tree(&tree).map_err(|e| e.to_string())?;
+        index.write(Default::default()).map_err(|e| e.to_string())
+    }
+
+    fn commit(&self, path: &Path, message: &str) -> Result<(), String> {
+        println!(
+            "│   {} - git commit -m {:?} (native)",
+            "Executing".purple(),
+            message
+        );
+
+        let repo = NativeGit::open(path)?;
+        let index = repo.open_index().map_err(|e| e.to_string())?;
+
+        let mut editor = repo
+            .edit_tree(gix::ObjectId::empty_tree(repo.object_hash()))
+            .map_err(|e| e.to_string())?;
+
+        for entry in index.entries() {
+            let kind = match entry.mode {
+                gix::index::entry::Mode::FILE_EXECUTABLE => {
+                    gix::object::tree::EntryKind::BlobExecutable
+                }
+                gix::index::entry::Mode::SYMLINK => gix::object::tree::EntryKind::Link,
+                gix::index::entry::Mode::COMMIT => gix::object::tree::EntryKind::Commit,
+                _ => gix::object::tree::EntryKind::Blob,
+            };
+
+            editor
+                .upsert(entry.path(&index), kind, entry.id)
+                .map_err(|e| e.to_string())?;
+        }
+
+        let tree = editor.write().map_err(|e| e.to_string())?;
+
+        // the commit step re-commits on top of HEAD, if the repository has commits already
+        let parents: Vec<gix::ObjectId> = repo
+            .head_id()
+            .ok()
+            .map(|id| id.detach())
+            .into_iter()
+            .collect();
+
+        repo.commit("HEAD", message, tree, parents)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn has_commits(&self, path: &Path) -> bool {
+        NativeGit::open(path).is_ok_and(|repo| repo.head_id().is_ok())
+    }
+
+    fn remote_urls(&self, path: &Path) -> Vec<String> {
+        let Ok(repo) = NativeGit::open(path) else {
+            return Vec::new();
+        };
+
+        repo.remote_names()
+            .iter()
+            .filter_map(|name| {
+                let remote = repo.find_remote(name.as_ref()).ok()?;
+                let url = remote.url(gix::remote::Direction::Fetch)?;
+                Some(url.to_bstring().to_string())
+            })
+            .collect()
+    }
+
+    fn submodule_add(&self, path: &Path, subpath: &str, url: &str) -> Result<String, String> {
+        // cloning requires the network features of gix; the `git` program is used instead
+        ShellGit.submodule_add(path, subpath, url)
+    }
+}
+
+#[cfg(all(test, feature = "native-git"))]
+mod tests {
+    use super::*;
+
+    /// perform `git <args>` command in `path`, and returns its output
+    fn git(path: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from(String::from_utf8_lossy(&output.stdout).trim())
+    }
+
+    #[test]
+    fn native_repository_is_readable_by_git() {
+        let path =
+            std::env::temp_dir().join(format!("createpy-test-native-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(path.join("src").join("demo")).unwrap();
+        std::fs::create_dir_all(path.join("venv")).unwrap();
+
+        std::fs::write(path.join("README.md"), "# demo\n").unwrap();
+        std::fs::write(path.join("src").join("demo").join("__init__.py"), "").unwrap();
+        std::fs::write(path.join("venv").join("pyvenv.cfg"), "").unwrap();
+        std::fs::write(path.join(".gitignore"), "venv/\n").unwrap();
+
+        let backend = NativeGit;
+        backend.init(&path, "main").unwrap();
+        backend.config(&path, "user.name", "test").unwrap();
+        backend
+            .config(&path, "user.email", "test@example.com")
+            .unwrap();
+        backend.add_all(&path, &[String::from("venv")]).unwrap();
+        backend.commit(&path, "Initial commit").unwrap();
+
+        assert!(backend.has_commits(&path));
+        assert_eq!(git(&path, &["status", "--porcelain"]), "");
+        assert_eq!(
+            git(&path, &["log", "-1", "--format=%an <%ae> %s"]),
+            "test <test@example.com> Initial commit"
+        );
+        assert_eq!(git(&path, &["symbolic-ref", "--short", "HEAD"]), "main");
+        assert_eq!(
+            git(&path, &["ls-files"]),
+            ".gitignore\nREADME.md\nsrc/demo/__init__.py"
+        );
+        git(&path, &["fsck", "--strict"]);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -0,0 +1,107 @@
+use colored::Colorize;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+/// Pre-create and post-create hook commands, and the environment which they run with
+#[derive(Debug, Default)]
+pub struct Hooks {
+    pub(crate) pre_create: Vec<String>,
+    pub(crate) post_create: Vec<String>,
+
+    /// Working directory of pre-create hooks
+    pub(crate) base: PathBuf,
+
+    /// Working directory of post-create hooks
+    pub(crate) projectpath: PathBuf,
+
+    /// Environment variables which are passed to hooks, e.g. `CREATEPY_PROJECT_DIR`
+    pub(crate) env: Vec<(&'static str, String)>,
+}
+
+/// Create a command which runs `script` by the system shell
+fn shell(script: &str) -> std::process::Command {
+    if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", script]);
+        command
+    } else {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+}
+
+/// Print lines of `reader` under the current progress heading
+fn stream(reader: impl std::io::Read) {
+    for line in BufReader::new(reader).lines() {
+        match line {
+            Ok(line) => println!("│       {}", line),
+            Err(_) => break,
+        }
+    }
+}
+
+impl Hooks {
+    /// Run `script` and stream its output
+    fn execute(&self, script: &str, directory: &PathBuf) -> Result<(), String> {
+        println!("│   {} - {}", "Executing".purple(), script);
+
+        let mut command = shell(script);
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+        command.current_dir(directory);
+        command.envs(self.env.iter().map(|(k, v)| (*k, v)));
+
+        let mut child = match command.spawn() {
+            Ok(o) => o,
+            Err(e) => {
+                return Err(format!("cannot run hook '{}': {}", script, e));
+            }
+        };
+
+        let stderr = child.stderr.take().unwrap();
+        let stderr_thread = std::thread::spawn(move || stream(stderr));
+        stream(child.stdout.take().unwrap());
+        let _ = stderr_thread.join();
+
+        let status = child.wait().unwrap();
+
+        if !status.success() {
+            return Err(format!(
+                "hook failed: '{}' [exit with {}]",
+                script,
+                status.code().unwrap_or(256)
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn run(&self, title: &str, scripts: &[String], directory: &PathBuf) -> Result<(), String> {
+        if scripts.is_empty() {
+            return Ok(());
+        }
+
+        println!("{}", title.bold());
+
+        for script in scripts {
+            if let Err(e) = self.execute(script, directory) {
+                println!("└── {} {}", "error:".red().bold(), e);
+                return Err(e);
+            }
+        }
+
+        println!("└── {}", "END\n".green().bold());
+        Ok(())
+    }
+
+    /// Run pre-create hooks, in the project base directory
+    pub fn pre_create(&self) -> Result<(), String> {
+        self.run("Running pre-create hooks ...", &self.pre_create, &self.base)
+    }
+
+    /// Run post-create hooks, in the project directory
+    pub fn post_create(&self) -> Result<(), String> {
+        self.run("Running post-create hooks ...", &self.post_create, &self.projectpath)
+    }
+}
@@ -0,0 +1,329 @@
+//! Create Python projects, a virtual environment, and an empty git repository for them.
+//!
+//! ```no_run
+//! use createpy::arguments::GitArguments;
+//! use createpy::ProjectBuilder;
+//!
+//! let mut git = GitArguments::new();
+//! git.branch = String::from("main");
+//!
+//! ProjectBuilder::new("myproject")
+//!     .git(git)
+//!     .prompt(|_warning| false)
+//!     .build()
+//!     .and_then(|scaffold| scaffold.run())
+//!     .unwrap();
+//! ```
+
+pub mod arguments;
+pub mod config;
+pub mod doctor;
+pub mod git;
+pub mod hooks;
+pub mod makers;
+pub mod pipeline;
+pub mod template;
+
+use config::Config;
+use makers::{Maker, Prompt};
+use pipeline::Pipeline;
+
+/// Builds a project from arguments; the makers are validated by [`ProjectBuilder::build`]
+/// and performed by [`Scaffold::run`]
+pub struct ProjectBuilder {
+    arguments: arguments::Arguments,
+    makers: Vec<Box<dyn Maker>>,
+    config: Config,
+    prompt: Box<Prompt>,
+    preflight: bool,
+}
+
+impl From<arguments::Arguments> for ProjectBuilder {
+    fn from(value: arguments::Arguments) -> Self {
+        ProjectBuilder {
+            arguments: value,
+            makers: Vec::new(),
+            config: Config::default(),
+            prompt: Box::new(|_| false),
+            preflight: true,
+        }
+    }
+}
+
+impl ProjectBuilder {
+    /// Create a builder for `name` project; git and virtual environment are disabled by default
+    pub fn new(name: &str) -> ProjectBuilder {
+        ProjectBuilder::from(arguments::Arguments::new(String::from(name)))
+    }
+
+    /// Set project description, used in the metadata and README
+    pub fn description(mut self, text: &str) -> Self {
+        self.arguments.project.description = String::from(text);
+        self
+    }
+
+    /// Set python version, e.g. `3.12`; defaults to the version of `python3`
+    pub fn python(mut self, version: &str) -> Self {
+        self.arguments.project.python = String::from(version);
+        self
+    }
+
+    /// Set package layout; `src` (default) or `flat`
+    pub fn layout(mut self, layout: &str) -> Self {
+        self.arguments.project.layout = String::from(layout);
+        self
+    }
+
+    /// Set README format; `md` (default) or `rst`
+    pub fn readme_format(mut self, format: &str) -> Self {
+        self.arguments.project.readme_format = String::from(format);
+        self
+    }
+
+    /// Set author name; defaults to the git user name
+    pub fn author(mut self, name: &str) -> Self {
+        self.arguments.project.author = String::from(name);
+        self
+    }
+
+    /// Create a LICENSE file; `id` is a SPDX identifier, see [`template::LICENSES`]
+    pub fn license(mut self, id: &str) -> Self {
+        self.arguments.project.license = String::from(id);
+        self
+    }
+
+    /// Create a `tests` package with a smoke test; `pytest`, `unittest` or `none` (default)
+    pub fn tests(mut self, framework: &str) -> Self {
+        self.arguments.tools.tests = String::from(framework);
+        self
+    }
+
+    /// Configure linters and formatters; `ruff`, `black+isort` or `flake8`
+    pub fn lint(mut self, preset: &str) -> Self {
+        self.arguments.tools.lint = String::from(preset);
+        self
+    }
+
+    /// Configure a type checker, `mypy` or `pyright`, with `basic` or `strict` strictness
+    pub fn typecheck(mut self, checker: &str, strictness: &str) -> Self {
+        self.arguments.tools.typecheck = String::from(checker);
+        self.arguments.tools.strictness = String::from(strictness);
+        self
+    }
+
+    /// Configure pre-commit hooks; requires git and the virtual environment
+    pub fn pre_commit(mut self, enabled: bool) -> Self {
+        self.arguments.tools.pre_commit = enabled;
+        self
+    }
+
+    /// Create a CI workflow; `github`, `gitlab`, `gitea` or `auto` (inferred from the git remote)
+    pub fn ci(mut self, service: &str, python_versions: &[&str]) -> Self {
+        self.arguments.tools.ci = String::from(service);
+        self.arguments.tools.ci_python = python_versions.iter().map(|v| String::from(*v)).collect();
+        self
+    }
+
+    /// Create a Dockerfile which installs dependencies by `tool` (`pip`, `uv` or `poetry`),
+    /// and optionally `compose.yaml`
+    pub fn docker(mut self, tool: &str, compose: bool) -> Self {
+        self.arguments.tools.docker = String::from(tool);
+        self.arguments.tools.compose = compose;
+        self
+    }
+
+    /// Lock the installed dependencies by `tool`; `pip`, `uv`, `manager` or `auto`
+    pub fn lock(mut self, tool: &str) -> Self {
+        self.arguments.tools.lock = String::from(tool);
+        self
+    }
+
+    /// Enable git repository creation
+    pub fn git(mut self, git: arguments::GitArguments) -> Self {
+        self.arguments.git = Some(git);
+        self
+    }
+
+    /// Enable virtual environment creation
+    pub fn venv(mut self, venv: arguments::VirtualEnvironmentArguments) -> Self {
+        self.arguments.venv = Some(venv);
+        self
+    }
+
+    /// Use configuration hooks and templates
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use a template defined in the configuration
+    pub fn template(mut self, name: &str) -> Self {
+        self.arguments.other.template = String::from(name);
+        self
+    }
+
+    /// Add a command which is run before creating anything
+    pub fn pre_create(mut self, script: &str) -> Self {
+        self.arguments.hooks.pre_create.push(String::from(script));
+        self
+    }
+
+    /// Add a command which is run after all steps, in the project directory
+    pub fn post_create(mut self, script: &str) -> Self {
+        self.arguments.hooks.post_create.push(String::from(script));
+        self
+    }
+
+    /// Perform only these steps
+    pub fn only(mut self, steps: &[&str]) -> Self {
+        self.arguments.other.only = steps.iter().map(|s| String::from(*s)).collect();
+        self
+    }
+
+    /// Don't perform these steps
+    pub fn skip(mut self, steps: &[&str]) -> Self {
+        self.arguments.other.skip = steps.iter().map(|s| String::from(*s)).collect();
+        self
+    }
+
+    /// Register a custom step, which is performed after the steps it depends on
+    pub fn maker(mut self, maker: Box<dyn Maker>) -> Self {
+        self.makers.push(maker);
+        self
+    }
+
+    /// Assume 'yes' as answer to all prompts
+    pub fn reinitialize_without_input(mut self, yes: bool) -> Self {
+        self.arguments.other.reinitialize_without_input = yes;
+        self
+    }
+
+    /// Check the required programs and permissions before performing any step; see
+    /// [`doctor::preflight`]. It's enabled by default.
+    pub fn preflight(mut self, enabled: bool) -> Self {
+        self.preflight = enabled;
+        self
+    }
+
+    /// Set the confirmation callback which is called when a maker is already initialized;
+    /// by default nothing is reinitialized.
+    pub fn prompt<F: FnMut(&str) -> bool + 'static>(mut self, prompt: F) -> Self {
+        self.prompt = Box::new(prompt);
+        self
+    }
+
+    /// Validate arguments and create makers
+    pub fn build(mut self) -> Result<Scaffold, String> {
+        self.config.apply(&mut self.arguments)?;
+
+        if self.preflight {
+            doctor::preflight(&self.arguments)?;
+        }
+
+        let reinitialize_without_input = self.arguments.other.reinitialize_without_input;
+        let only = self.arguments.other.only.clone();
+        let skip = self.arguments.other.skip.clone();
+
+        let mut pipeline = Pipeline::try_from(self.arguments)?;
+
+        for maker in self.makers {
+            pipeline.register(maker)?;
+        }
+
+        pipeline.select(&only, &skip)?;
+
+        Ok(Scaffold {
+            pipeline,
+            reinitialize_without_input,
+            prompt: self.prompt,
+        })
+    }
+}
+
+/// Builds a workspace package of an existing project, under its `packages` directory
+pub struct PackageBuilder {
+    arguments: arguments::PackageArguments,
+    config: Config,
+    prompt: Box<Prompt>,
+}
+
+impl From<arguments::PackageArguments> for PackageBuilder {
+    fn from(value: arguments::PackageArguments) -> Self {
+        PackageBuilder {
+            arguments: value,
+            config: Config::default(),
+            prompt: Box::new(|_| false),
+        }
+    }
+}
+
+impl PackageBuilder {
+    /// Create a builder for `name` package of the project in the current directory
+    pub fn new(name: &str) -> PackageBuilder {
+        PackageBuilder::from(arguments::PackageArguments::new(String::from(name)))
+    }
+
+    /// Set package description, used in the metadata and README
+    pub fn description(mut self, text: &str) -> Self {
+        self.arguments.description = String::from(text);
+        self
+    }
+
+    /// Set the root project directory
+    pub fn root(mut self, path: &str) -> Self {
+        self.arguments.root = String::from(path);
+        self
+    }
+
+    /// Use configuration hooks and templates
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Use a template defined in the configuration
+    pub fn template(mut self, name: &str) -> Self {
+        self.arguments.template = String::from(name);
+        self
+    }
+
+    /// Assume 'yes' as answer to all prompts
+    pub fn reinitialize_without_input(mut self, yes: bool) -> Self {
+        self.arguments.reinitialize_without_input = yes;
+        self
+    }
+
+    /// Set the confirmation callback; see [`ProjectBuilder::prompt`]
+    pub fn prompt<F: FnMut(&str) -> bool + 'static>(mut self, prompt: F) -> Self {
+        self.prompt = Box::new(prompt);
+        self
+    }
+
+    /// Read the root project and create makers
+    pub fn build(mut self) -> Result<Scaffold, String> {
+        self.config.apply_package(&mut self.arguments)?;
+
+        let reinitialize_without_input = self.arguments.reinitialize_without_input;
+
+        Ok(Scaffold {
+            pipeline: Pipeline::try_from(self.arguments)?,
+            reinitialize_without_input,
+            prompt: self.prompt,
+        })
+    }
+}
+
+/// Validated makers, ready to perform
+pub struct Scaffold {
+    pipeline: Pipeline,
+    reinitialize_without_input: bool,
+    prompt: Box<Prompt>,
+}
+
+impl Scaffold {
+    /// Perform all makers in dependency order; progress is printed to stdout
+    pub fn run(mut self) -> Result<(), String> {
+        self.pipeline
+            .run(self.reinitialize_without_input, &mut self.prompt)
+    }
+}
@@ -1,61 +1,103 @@
 use colored::Colorize;
+use std::io::Write;
 use std::process::ExitCode;
 
-use makers::{call_reinitializer, MakersStructure};
+use createpy::{arguments, config::Config, doctor, PackageBuilder, ProjectBuilder, Scaffold};
 
-mod arguments;
-mod makers;
+fn confirm(_warning: &str) -> bool {
+    print!("│   do you to create it again {}? ", "(y/n)".bold());
+    std::io::stdout().flush().unwrap();
 
-fn main() -> ExitCode {
-    let args: arguments::Arguments = arguments::parse_args();
+    let mut buf = String::new();
+    std::io::stdin().read_line(&mut buf).unwrap();
 
-    let reinitialize_without_input = args.3.reinitialize_without_input;
+    buf = buf.trim().to_lowercase().to_string();
 
-    let initializers: MakersStructure = match MakersStructure::try_from(args) {
-        Ok(o) => o,
-        Err(e) => {
-            println!("{} {}", "error:".red().bold(), e);
-            return ExitCode::FAILURE;
-        }
-    };
-    // │
-    println!("{}", "Creating project ...".bold());
-    match call_reinitializer(&initializers.project, reinitialize_without_input) {
-        Ok(_) => (),
-        Err(e) => {
-            println!("└── {} {}", "error:".red().bold(), e);
-            return ExitCode::FAILURE;
-        }
+    buf == "y" || buf == "yes"
+}
+
+/// Validate the command line, and create makers
+fn build(command: arguments::Command) -> Result<Scaffold, String> {
+    match command {
+        arguments::Command::AddPackage(args) => PackageBuilder::from(args)
+            .config(Config::load()?)
+            .prompt(confirm)
+            .build(),
+        arguments::Command::Project(args) => ProjectBuilder::from(*args)
+            .config(Config::load()?)
+            .prompt(confirm)
+            .build(),
+        _ => Err(String::from("not a project command")),
     }
-    println!("└── {}", "END\n".green().bold());
+}
 
-    if let Some(git) = initializers.git {
-        println!("{}", "Initializing git ...".bold());
-        
-        match call_reinitializer(&git, reinitialize_without_input) {
-            Ok(_) => (),
-            Err(e) => {
-                println!("└── {} {}", "error:".red().bold(), e);
-                return ExitCode::FAILURE;
-            }
-        }
-        
-        println!("└── {}", "END\n".green().bold());
+/// Print the templates which are defined in the config file
+fn templates() -> Result<(), String> {
+    let config = Config::load()?;
+
+    println!("{}", "Templates".bold());
+
+    if config.templates.is_empty() {
+        println!("│   no templates are defined");
     }
 
-    if let Some(venv) = initializers.venv {
-        println!("{}", "Creating virtual environment ...".bold());
-        
-        match call_reinitializer(&venv, reinitialize_without_input) {
-            Ok(_) => (),
-            Err(e) => {
-                println!("└── {} {}", "error:".red().bold(), e);
-                return ExitCode::FAILURE;
+    for (name, template) in &config.templates {
+        println!(
+            "│   {}: {} pre-create hooks, {} post-create hooks",
+            name,
+            template.hooks.pre_create.len(),
+            template.hooks.post_create.len()
+        );
+    }
+
+    println!("└── {}", "END\n".green().bold());
+    Ok(())
+}
+
+/// Print the config file path and its content
+fn config() -> Result<(), String> {
+    // the file is validated before printing it
+    Config::load()?;
+
+    println!("{}", "Configuration".bold());
+
+    match Config::path() {
+        Some(path) if path.is_file() => {
+            println!("│   path: {}", path.display());
+
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+            for line in content.lines() {
+                println!("│       {}", line);
             }
         }
-        
-        println!("└── {}", "END\n".green().bold());
+        Some(path) => println!("│   path: {} (not found)", path.display()),
+        None => println!("│   path: unknown; set CREATEPY_CONFIG"),
     }
 
-    ExitCode::SUCCESS
+    println!("└── {}", "END\n".green().bold());
+    Ok(())
+}
+
+/// Print `e`; the pipeline prints its own errors, under the failed step
+fn report(e: String) -> String {
+    println!("{} {}", "error:".red().bold(), e);
+    e
+}
+
+fn main() -> ExitCode {
+    let result = match arguments::parse_args() {
+        arguments::Command::Doctor => doctor::run(std::path::Path::new(".")),
+        arguments::Command::Templates => templates().map_err(report),
+        arguments::Command::Config => config().map_err(report),
+        command => build(command)
+            .map_err(report)
+            .and_then(|scaffold| scaffold.run()),
+    };
+
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::FAILURE,
+    }
 }
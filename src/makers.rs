@@ -1,8 +1,12 @@
 use colored::Colorize;
 
 use crate::arguments;
-use std::io::Write;
-use std::path::PathBuf;
+use crate::git::{self, GitBackend};
+use crate::hooks::Hooks;
+use crate::pipeline::Pipeline;
+use crate::template;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 
 /// ReInitializer returning type
 pub enum ReInitializerResult {
@@ -16,7 +20,7 @@ pub enum ReInitializerResult {
     Err(String),
 }
 
-pub trait ReInitializer: Sized {
+pub trait ReInitializer {
     /// Try to perform and initialize
     fn initialize(&self) -> ReInitializerResult;
 
@@ -24,18 +28,40 @@ pub trait ReInitializer: Sized {
     fn reinitialize(&self) -> ReInitializerResult;
 }
 
+/// A pipeline step; see [`Pipeline`]
+pub trait Maker: ReInitializer {
+    /// Step name, used for selecting steps (`--only` and `--skip`) and in dependencies
+    fn name(&self) -> &'static str;
+
+    /// Heading which is printed before performing the step
+    fn title(&self) -> &'static str;
+
+    /// Names of the steps which have to be performed before this one
+    fn dependencies(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Result which is reported in the final summary, after performing the step
+    fn summary(&self) -> Option<String> {
+        None
+    }
+}
+
 /// Project maker, controls project directory and anything related
 #[derive(Debug)]
 pub struct Project {
     name: String,
     base: std::path::PathBuf,
+
+    /// The project directory already exists, and it's used as is
+    in_place: bool,
 }
 
 impl TryFrom<arguments::ProjectArguments> for Project {
     type Error = String;
 
     fn try_from(value: arguments::ProjectArguments) -> Result<Self, Self::Error> {
-        let mut pieces = value.0.rsplitn(2, std::path::MAIN_SEPARATOR);
+        let mut pieces = value.name.rsplitn(2, std::path::MAIN_SEPARATOR);
 
         let name = String::from(pieces.next().unwrap());
 
@@ -60,7 +86,15 @@ impl TryFrom<arguments::ProjectArguments> for Project {
             ));
         }
 
-        Ok(Project { name, base })
+        if value.in_place && !base.join(&name).is_dir() {
+            return Err(format!("no such directory: {}", base.join(&name).display()));
+        }
+
+        Ok(Project {
+            name,
+            base,
+            in_place: value.in_place,
+        })
     }
 }
 
@@ -69,12 +103,88 @@ impl Project {
     fn full_path(&self) -> PathBuf {
         self.base.join(self.name.clone())
     }
+
+    /// Importable python package name, e.g. `my_project` for `My-Project`
+    pub fn package_name(&self) -> String {
+        package_name(&self.name)
+    }
+}
+
+/// Returns the project name, without the base directory
+fn project_name(value: &arguments::ProjectArguments) -> String {
+    match value.name.rsplit(std::path::MAIN_SEPARATOR).next() {
+        Some(name) => String::from(name),
+        None => value.name.clone(),
+    }
+}
+
+/// Returns the importable python package name of `name` project; a leading digit is
+/// prefixed with `_`, e.g. `_2024_notes`
+fn package_name(name: &str) -> String {
+    let result: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", result)
+    } else {
+        result
+    }
+}
+
+/// Format `value` as a TOML string, e.g. `"a"`
+fn toml_string(value: &str) -> String {
+    toml::Value::String(String::from(value)).to_string()
+}
+
+/// Format `items` as the content of a TOML array, e.g. `"a", "b"`
+fn quoted_list(items: &[String]) -> String {
+    items
+        .iter()
+        .map(|i| toml_string(i))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Format `text` as a python docstring; quotes, backslashes and control characters are
+/// escaped, so it cannot end the string early
+fn python_docstring(text: &str) -> String {
+    let mut result = String::from("\"\"\"");
+
+    for c in text.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' | '\t' => result.push(c),
+            c if c.is_control() => result.push_str(&format!("\\x{:02x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push_str("\"\"\"");
+    result
+}
+
+/// Returns the README file name of `format`
+fn readme_file(format: &str) -> String {
+    if format == "rst" {
+        String::from("README.rst")
+    } else {
+        String::from("README.md")
+    }
 }
 
 impl ReInitializer for Project {
     fn initialize(&self) -> ReInitializerResult {
         let target = self.full_path();
 
+        if self.in_place {
+            println!("│   Using directory: {}", target.display());
+            return ReInitializerResult::Ok;
+        }
+
         match target.try_exists() {
             Ok(exists) => {
                 if exists {
@@ -89,7 +199,7 @@ impl ReInitializer for Project {
         }
 
         println!("│   Creating directory: {}", target.display());
-        std::fs::create_dir(target).unwrap();
+        std::fs::create_dir_all(target).unwrap();
         ReInitializerResult::Ok
     }
 
@@ -100,20 +210,63 @@ impl ReInitializer for Project {
         std::fs::remove_dir_all(&target).unwrap();
 
         println!("│   Creating directory: {}", target.display());
-        std::fs::create_dir(target).unwrap();
+        std::fs::create_dir_all(target).unwrap();
         ReInitializerResult::Ok
     }
 }
 
+impl Maker for Project {
+    fn name(&self) -> &'static str {
+        "project"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating project ..."
+    }
+}
+
+/// Remote of the git repository
+#[derive(Debug)]
+struct Remote {
+    name: String,
+    url: String,
+
+    /// Push URL, if it differs from `url`
+    push_url: String,
+}
+
+/// Parse `name=url` entry of `option`
+fn parse_named_url<'a>(entry: &'a str, option: &str) -> Result<(&'a str, &'a str), String> {
+    match entry.split_once('=') {
+        Some((name, url)) if !url.trim().is_empty() => Ok((name.trim(), url.trim())),
+        _ => Err(format!(
+            "invalid {}: '{}'; expected name=url",
+            option, entry
+        )),
+    }
+}
+
 /// Git maker, controls project git configuration
 #[derive(Debug)]
 pub struct Git {
     user_name: String,
     user_email: String,
-    remote_url: String,
-    remote_name: String,
+    remotes: Vec<Remote>,
     branch: String,
 
+    /// Configuration entries, `(key, value)`; applied in order
+    config: Vec<(String, String)>,
+
+    /// Create `.gitattributes` file
+    attributes: bool,
+
+    /// Submodules, `(path, url)`; added after all configs
+    submodules: Vec<(String, String)>,
+    backend: Box<dyn GitBackend>,
+
+    /// Added submodules and their commits, reported in the summary
+    added: RefCell<Vec<String>>,
+
     /// **Note:** this value have to be setted manually
     projectpath: PathBuf,
 }
@@ -122,88 +275,201 @@ impl TryFrom<arguments::GitArguments> for Git {
     type Error = String;
 
     fn try_from(value: arguments::GitArguments) -> Result<Self, Self::Error> {
+        if !value.branch.is_empty() {
+            check_branch_name(&value.branch)?;
+        }
+
+        let mut config = Vec::with_capacity(value.config.len());
+
+        for entry in &value.config {
+            let Some((key, v)) = entry.split_once('=') else {
+                return Err(format!(
+                    "invalid git config: '{}'; expected key=value",
+                    entry
+                ));
+            };
+
+            check_config_key(key.trim())?;
+            config.push((String::from(key.trim()), String::from(v.trim())));
+        }
+
+        let mut remotes: Vec<Remote> = Vec::new();
+
+        if !value.remote_url.is_empty() {
+            remotes.push(Remote {
+                name: if !value.remote_name.is_empty() {
+                    value.remote_name
+                } else {
+                    String::from("origin")
+                },
+                url: value.remote_url,
+                push_url: String::new(),
+            });
+        }
+
+        for entry in &value.remotes {
+            let (name, url) = parse_named_url(entry, "remote")?;
+
+            remotes.push(Remote {
+                name: String::from(name),
+                url: String::from(url),
+                push_url: String::new(),
+            });
+        }
+
+        for (index, remote) in remotes.iter().enumerate() {
+            check_remote_name(&remote.name)?;
+
+            if remotes[..index].iter().any(|r| r.name == remote.name) {
+                return Err(format!("duplicate remote: '{}'", remote.name));
+            }
+        }
+
+        for entry in &value.push_urls {
+            let (name, url) = parse_named_url(entry, "push URL")?;
+
+            let Some(remote) = remotes.iter_mut().find(|r| r.name == name) else {
+                return Err(format!("push URL of an unknown remote: '{}'", name));
+            };
+
+            if !remote.push_url.is_empty() {
+                return Err(format!("duplicate push URL of remote '{}'", name));
+            }
+
+            remote.push_url = String::from(url);
+        }
+
+        let mut submodules: Vec<(String, String)> = Vec::new();
+
+        for entry in &value.submodules {
+            let (path, url) = parse_named_url(entry, "submodule")?;
+            check_submodule_path(path)?;
+
+            if submodules.iter().any(|(p, _)| p == path) {
+                return Err(format!("duplicate submodule: '{}'", path));
+            }
+
+            submodules.push((String::from(path), String::from(url)));
+        }
+
         Ok(Git {
             user_name: value.user_name,
             user_email: value.user_email,
-            remote_url: value.remote_url,
-            remote_name: if !value.remote_name.is_empty() {
-                value.remote_name
-            } else {
-                String::from("origin")
-            },
+            remotes,
             branch: if !value.branch.is_empty() {
                 value.branch
             } else {
                 String::from("main")
             },
+            config,
+            attributes: value.attributes,
+            submodules,
+            backend: git::backend(),
+            added: RefCell::new(Vec::new()),
             projectpath: PathBuf::new(),
         })
     }
 }
 
-impl Git {
-    /// perform `git init -b <branch>` command
-    fn init(&self) -> Result<(), String> {
-        println!("│   {} - git init -b {}", "Executing".purple(), self.branch);
-
-        let mut command = std::process::Command::new("git");
-        command.stdout(std::process::Stdio::null());
-        command.stderr(std::process::Stdio::piped());
-        command.args(["init", "-b", self.branch.as_str()]);
-        command.current_dir(&self.projectpath);
+/// Validate `key` of `git config`; `<section>[.<subsection>].<name>`, where the section and
+/// name are alphanumeric (or `-`), and the name starts with a letter
+fn check_config_key(key: &str) -> Result<(), String> {
+    let valid = |part: &str| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
 
-        let child = match command.spawn() {
-            Ok(o) => o,
-            Err(_) => {
-                return Err(String::from("command not found: 'git'"));
-            }
-        };
+    let (section, rest) = key.split_once('.').unwrap_or((key, ""));
+    let name = rest.rsplit_once('.').map_or(rest, |(_, name)| name);
 
-        let status = child.wait_with_output().unwrap();
+    if valid(section) && valid(name) && name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid git config key: '{}'; expected <section>.<name>, e.g. 'pull.rebase'",
+            key
+        ))
+    }
+}
 
-        if !status.status.success() {
-            return Err(format!(
-                "{} [exit with {}]",
-                String::from_utf8_lossy(&status.stderr),
-                status.status.code().unwrap_or(256)
-            ));
-        }
+/// Validate `path` of a submodule; it's a relative path inside the project
+fn check_submodule_path(path: &str) -> Result<(), String> {
+    let inside = Path::new(path)
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(name) if name != ".git"));
 
-        Ok(())
+    if path.is_empty() || !inside {
+        return Err(format!(
+            "invalid submodule path: '{}'; it must be a relative path inside the project",
+            path
+        ));
     }
 
-    /// perform `git config user.name <user_name>` command
-    fn config_name(&self) -> Result<(), String> {
-        if self.user_name.is_empty() {
-            return Ok(());
-        }
+    Ok(())
+}
 
-        println!("│   {} - git config user.name {}", "Executing".purple(), self.user_name);
+/// Validate `name` by the rules of `git check-ref-format --branch`
+fn check_branch_name(name: &str) -> Result<(), String> {
+    match ref_name_error(name) {
+        Some(reason) => Err(format!("invalid branch name: '{}'; {}", name, reason)),
+        None => Ok(()),
+    }
+}
 
-        let mut command = std::process::Command::new("git");
-        command.stdout(std::process::Stdio::null());
-        command.stderr(std::process::Stdio::piped());
-        command.args(["config", "user.name", self.user_name.as_str()]);
-        command.current_dir(&self.projectpath);
+/// Validate remote `name`; it's a part of `refs/remotes/<name>/...` references
+fn check_remote_name(name: &str) -> Result<(), String> {
+    let reason = if name.is_empty() {
+        Some("it cannot be empty")
+    } else {
+        ref_name_error(name)
+    };
 
-        let child = match command.spawn() {
-            Ok(o) => o,
-            Err(_) => {
-                return Err(String::from("command not found: 'git'"));
-            }
-        };
+    match reason {
+        Some(reason) => Err(format!("invalid remote name: '{}'; {}", name, reason)),
+        None => Ok(()),
+    }
+}
 
-        let status = child.wait_with_output().unwrap();
+/// Returns the reason if `name` cannot be a part of a git reference
+fn ref_name_error(name: &str) -> Option<&'static str> {
+    if name.starts_with('-') {
+        Some("it cannot start with '-'")
+    } else if name == "HEAD" || name == "@" {
+        Some("it's reserved")
+    } else if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        Some("it cannot have empty components")
+    } else if name.ends_with('.') {
+        Some("it cannot end with '.'")
+    } else if name.contains("..") || name.contains("@{") {
+        Some("it cannot contain '..' or '@{'")
+    } else if name
+        .chars()
+        .any(|c| c.is_ascii_control() || " ~^:?*[\\".contains(c))
+    {
+        Some("it cannot contain spaces, control characters or any of '~^:?*[\\'")
+    } else if name
+        .split('/')
+        .any(|c| c.starts_with('.') || c.ends_with(".lock"))
+    {
+        Some("its components cannot start with '.' or end with '.lock'")
+    } else {
+        None
+    }
+}
 
-        if !status.status.success() {
-            return Err(format!(
-                "{} [exit with {}]",
-                String::from_utf8_lossy(&status.stderr),
-                status.status.code().unwrap_or(256)
-            ));
+impl Git {
+    /// perform `git init -b <branch>` command
+    fn init(&self) -> Result<(), String> {
+        self.backend.init(&self.projectpath, &self.branch)
+    }
+
+    /// perform `git config user.name <user_name>` command
+    fn config_name(&self) -> Result<(), String> {
+        if self.user_name.is_empty() {
+            return Ok(());
         }
 
-        Ok(())
+        self.backend
+            .config(&self.projectpath, "user.name", &self.user_name)
     }
 
     /// perform `git config user.email <user_email>` command
@@ -212,72 +478,61 @@ impl Git {
             return Ok(());
         }
 
-        println!("│   {} - git config user.email {}", "Executing".purple(), self.user_email);
+        self.backend
+            .config(&self.projectpath, "user.email", &self.user_email)
+    }
 
-        let mut command = std::process::Command::new("git");
-        command.stdout(std::process::Stdio::null());
-        command.stderr(std::process::Stdio::piped());
-        command.args(["config", "user.email", self.user_email.as_str()]);
-        command.current_dir(&self.projectpath);
+    /// perform `git remote add <name> <url>` command for each remote, and set its push URL
+    fn config_remote(&self) -> Result<(), String> {
+        for remote in &self.remotes {
+            self.backend
+                .remote_add(&self.projectpath, &remote.name, &remote.url)?;
 
-        let child = match command.spawn() {
-            Ok(o) => o,
-            Err(_) => {
-                return Err(String::from("command not found: 'git'"));
+            if !remote.push_url.is_empty() {
+                self.backend.config(
+                    &self.projectpath,
+                    &format!("remote.{}.pushurl", remote.name),
+                    &remote.push_url,
+                )?;
             }
-        };
+        }
 
-        let status = child.wait_with_output().unwrap();
+        Ok(())
+    }
 
-        if !status.status.success() {
-            return Err(format!(
-                "{} [exit with {}]",
-                String::from_utf8_lossy(&status.stderr),
-                status.status.code().unwrap_or(256)
-            ));
+    /// perform `git config <key> <value>` command for each of `self.config`, in order
+    fn config_entries(&self) -> Result<(), String> {
+        for (key, value) in &self.config {
+            self.backend.config(&self.projectpath, key, value)?;
         }
 
         Ok(())
     }
 
-    /// perform `git remote add <remote_name> <remote_url>` command
-    fn config_remote(&self) -> Result<(), String> {
-        if self.remote_url.is_empty() {
+    /// create `.gitattributes` file; an existing one is kept
+    fn create_attributes(&self) -> Result<(), String> {
+        if !self.attributes {
             return Ok(());
         }
 
-        if self.remote_name.is_empty() {
-            panic!("config_remote: self.remote_name is empty!");
-        }
-
-        println!("│   {} - git remote add {} {}", "Executing".purple(), self.remote_name, self.remote_url);
+        let path = self.projectpath.join(".gitattributes");
 
-        let mut command = std::process::Command::new("git");
-        command.stdout(std::process::Stdio::null());
-        command.stderr(std::process::Stdio::piped());
-        command.args([
-            "remote",
-            "add",
-            self.remote_name.as_str(),
-            self.remote_url.as_str(),
-        ]);
-        command.current_dir(&self.projectpath);
+        if path.exists() {
+            println!("│   Keeping file: {}", path.display());
+            return Ok(());
+        }
 
-        let child = match command.spawn() {
-            Ok(o) => o,
-            Err(_) => {
-                return Err(String::from("command not found: 'git'"));
-            }
-        };
+        write_file(&path, include_str!("templates/gitattributes"))
+    }
 
-        let status = child.wait_with_output().unwrap();
+    /// perform `git submodule add <url> <path>` command for each submodule
+    fn add_submodules(&self) -> Result<(), String> {
+        let mut added = self.added.borrow_mut();
+        added.clear();
 
-        if !status.status.success() {
-            return Err(format!(
-                "{} [exit with {}]",
-                String::from_utf8_lossy(&status.stderr),
-                status.status.code().unwrap_or(256)
-            ));
+        for (path, url) in &self.submodules {
+            let commit = self.backend.submodule_add(&self.projectpath, path, url)?;
+            added.push(format!("{} ({})", path, commit));
         }
 
         Ok(())
@@ -288,7 +543,10 @@ impl Git {
         self.init()?;
         self.config_name()?;
         self.config_email()?;
-        self.config_remote()
+        self.config_remote()?;
+        self.config_entries()?;
+        self.create_attributes()?;
+        self.add_submodules()
     }
 }
 
@@ -328,18 +586,77 @@ impl ReInitializer for Git {
     }
 }
 
+impl Maker for Git {
+    fn name(&self) -> &'static str {
+        "git"
+    }
+
+    fn title(&self) -> &'static str {
+        "Initializing git ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+
+    fn summary(&self) -> Option<String> {
+        let added = self.added.borrow();
+
+        if added.is_empty() {
+            None
+        } else {
+            Some(format!("submodules: {}", added.join(", ")))
+        }
+    }
+}
+
+/// Supported project managers; all except `pip` create an in-project `.venv`
+const PROJECT_MANAGERS: [&str; 4] = ["pip", "poetry", "pdm", "hatch"];
+
+/// Scripts which create the virtual environment of `pip` manager; `native` creates it
+/// without any script, like `python -m venv`
+const VENV_SCRIPTS: [&str; 3] = ["virtualenv", "venv", "native"];
+
+/// Prints the base interpreter and its version; `sys._base_executable` is the interpreter
+/// which created the virtual environment, if python runs in one
+const BASE_INTERPRETER: &str = "import os, sys
+print(os.path.realpath(getattr(sys, '_base_executable', sys.executable)))
+print('%d.%d.%d' % sys.version_info[:3])";
+
 /// Virtual environment maker
 #[derive(Debug)]
 pub struct VirtualEnvironment {
+    manager: String,
     script: String,
     path: PathBuf,
+    without_pip: bool,
+
+    /// Project directory; the project managers are run in it
+    projectpath: PathBuf,
+
+    /// Python interpreter which creates the virtual environment, e.g. `python3.12`
+    python: String,
+
+    /// Packages which are installed after creating the virtual environment, e.g. the linter
+    /// and the test framework
+    packages: Vec<String>,
 }
 
 impl TryFrom<arguments::VirtualEnvironmentArguments> for VirtualEnvironment {
     type Error = String;
 
     fn try_from(value: arguments::VirtualEnvironmentArguments) -> Result<Self, Self::Error> {
-        if value.script != "venv" && value.script != "virtualenv" {
+        let manager = if value.manager.is_empty() {
+            String::from("pip")
+        } else {
+            value.manager
+        };
+
+        if !PROJECT_MANAGERS.contains(&manager.as_str()) {
+            return Err(format!("not supported project manager: {}", manager));
+        }
+
+        if manager == "pip" && !VENV_SCRIPTS.contains(&value.script.as_str()) {
             return Err(format!(
                 "not supported script for creating virtual environment: {}",
                 value.script
@@ -347,12 +664,17 @@ impl TryFrom<arguments::VirtualEnvironmentArguments> for VirtualEnvironment {
         }
 
         Ok(VirtualEnvironment {
+            manager,
             script: value.script,
+            without_pip: value.without_pip,
+            projectpath: PathBuf::new(),
             path: if value.path.is_empty() {
                 PathBuf::from(".")
             } else {
                 PathBuf::from(value.path)
             },
+            python: String::from("python3"),
+            packages: Vec::new(),
         })
     }
 }
@@ -360,18 +682,26 @@ impl TryFrom<arguments::VirtualEnvironmentArguments> for VirtualEnvironment {
 impl VirtualEnvironment {
     /// create the virtual environment with `venv` script
     fn venv(&self) -> Result<(), String> {
-        println!("│   {} - python3 -m venv {}", "Executing".purple(), self.path.display());
+        println!(
+            "│   {} - {} -m venv {}",
+            "Executing".purple(),
+            self.python,
+            self.path.display()
+        );
 
-        let mut command = std::process::Command::new("python3");
+        let mut command = std::process::Command::new(&self.python);
         command.stdout(std::process::Stdio::null());
         command.stderr(std::process::Stdio::piped());
         command.args(["-m", "venv"]);
+        if self.without_pip {
+            command.arg("--without-pip");
+        }
         command.arg(self.path.clone());
 
         let child = match command.spawn() {
             Ok(o) => o,
             Err(_) => {
-                return Err(String::from("command not found: 'python3'"));
+                return Err(format!("command not found: '{}'", self.python));
             }
         };
 
@@ -390,12 +720,20 @@ impl VirtualEnvironment {
 
     /// create the virtual environment with `virtualenv` script
     fn virtualenv(&self) -> Result<(), String> {
-        println!("│   {} - virtualenv --no-vcs-ignore {}", "Executing".purple(), self.path.display());
+        println!(
+            "│   {} - virtualenv --no-vcs-ignore -p {} {}",
+            "Executing".purple(),
+            self.python,
+            self.path.display()
+        );
 
         let mut command = std::process::Command::new("virtualenv");
         command.stdout(std::process::Stdio::null());
         command.stderr(std::process::Stdio::piped());
-        command.args(["--no-vcs-ignore"]);
+        command.args(["--no-vcs-ignore", "-p", self.python.as_str()]);
+        if self.without_pip {
+            command.args(["--no-pip", "--no-setuptools", "--no-wheel"]);
+        }
         command.arg(self.path.clone());
 
         let child = match command.spawn() {
@@ -418,121 +756,2944 @@ impl VirtualEnvironment {
         Ok(())
     }
 
-    fn create(&self) -> Result<(), String> {
-        if self.script == "venv" {
-            self.venv()
-        } else if self.script == "virtualenv" {
-            self.virtualenv()
-        } else {
-            Err(format!("not supported script: '{}'", self.script))
+    /// create the virtual environment without any script: `pyvenv.cfg`, the interpreter
+    /// symlinks, `site-packages` and activation scripts; pip is installed by `ensurepip`
+    #[cfg(unix)]
+    fn native(&self) -> Result<(), String> {
+        let mut command = std::process::Command::new(&self.python);
+        command.args(["-c", BASE_INTERPRETER]);
+
+        let output = command_output(command, &self.python)?;
+        let mut lines = output.lines();
+
+        let (Some(executable), Some(version)) = (lines.next(), lines.next()) else {
+            return Err(format!(
+                "cannot find the base interpreter of {}",
+                self.python
+            ));
+        };
+
+        let executable = PathBuf::from(executable);
+        let short_version = version.rsplit_once('.').map_or(version, |(v, _)| v);
+        let home = executable.parent().unwrap_or(Path::new("/"));
+
+        let bin = self.path.join("bin");
+        let site_packages = self
+            .path
+            .join("lib")
+            .join(format!("python{}", short_version))
+            .join("site-packages");
+
+        for directory in [&bin, &self.path.join("include"), &site_packages] {
+            std::fs::create_dir_all(directory)
+                .map_err(|e| format!("cannot create {}: {}", directory.display(), e))?;
         }
-    }
-}
 
-impl ReInitializer for VirtualEnvironment {
-    fn initialize(&self) -> ReInitializerResult {
-        let to_check = self.path.join("pyvenv.cfg");
+        // like `venv`, 64-bit linux environments have `lib64` too
+        if cfg!(all(target_os = "linux", target_pointer_width = "64")) {
+            let lib64 = self.path.join("lib64");
 
-        match to_check.try_exists() {
-            Ok(exists) => {
-                if exists {
-                    return ReInitializerResult::Warning(String::from(
-                        "virtual environment is already exists",
-                    ));
-                }
+            if !lib64.exists() {
+                std::os::unix::fs::symlink("lib", &lib64)
+                    .map_err(|e| format!("cannot create {}: {}", lib64.display(), e))?;
             }
-            Err(e) => {
-                return ReInitializerResult::Err(e.to_string());
+        }
+
+        write_file(
+            &self.path.join("pyvenv.cfg"),
+            &format!(
+                "home = {}\ninclude-system-site-packages = false\nversion = {}\nexecutable = {}\n",
+                home.display(),
+                version,
+                executable.display()
+            ),
+        )?;
+
+        for name in [
+            String::from("python"),
+            String::from("python3"),
+            format!("python{}", short_version),
+        ] {
+            let link = bin.join(name);
+
+            println!(
+                "│   Creating symlink: {} -> {}",
+                link.display(),
+                executable.display()
+            );
+
+            if link.symlink_metadata().is_ok() {
+                std::fs::remove_file(&link).map_err(|e| e.to_string())?;
             }
+
+            std::os::unix::fs::symlink(&executable, &link)
+                .map_err(|e| format!("cannot create {}: {}", link.display(), e))?;
         }
 
-        match self.create() {
-            Ok(_) => ReInitializerResult::Ok,
-            Err(e) => ReInitializerResult::Err(e),
+        let venv = std::path::absolute(&self.path).map_err(|e| e.to_string())?;
+        let prompt = venv
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        for (name, text) in template::ACTIVATE_SCRIPTS {
+            write_file(
+                &bin.join(name),
+                &template::render(
+                    text,
+                    &[("venv", &venv.to_string_lossy()), ("prompt", &prompt)],
+                ),
+            )?;
         }
-    }
 
-    fn reinitialize(&self) -> ReInitializerResult {
-        match self.create() {
-            Ok(_) => ReInitializerResult::Ok,
-            Err(e) => ReInitializerResult::Err(e),
+        if self.without_pip {
+            return Ok(());
         }
-    }
-}
 
-pub struct MakersStructure {
-    pub project: Project,
-    pub git: Option<Git>,
-    pub venv: Option<VirtualEnvironment>,
-}
+        let python = venv_python(&self.path);
 
-impl TryFrom<arguments::Arguments> for MakersStructure {
-    type Error = String;
+        println!(
+            "│   {} - {} -m ensurepip --upgrade --default-pip",
+            "Executing".purple(),
+            python.display()
+        );
 
-    fn try_from(value: arguments::Arguments) -> Result<Self, Self::Error> {
-        let project: Project = match Project::try_from(value.0) {
-            Ok(o) => o,
-            Err(e) => {
-                return Err(e);
+        let mut command = std::process::Command::new(&python);
+        command.args(["-m", "ensurepip", "--upgrade", "--default-pip"]);
+
+        run_command(command, &python.display().to_string())
+    }
+
+    #[cfg(not(unix))]
+    fn native(&self) -> Result<(), String> {
+        Err(String::from(
+            "'native' script is supported only on unix; use 'venv' instead",
+        ))
+    }
+
+    /// perform `<manager> <args>` command in the project directory
+    fn manage(&self, args: &[&str]) -> Result<(), String> {
+        println!(
+            "│   {} - {} {}",
+            "Executing".purple(),
+            self.manager,
+            args.join(" ")
+        );
+
+        let mut command = std::process::Command::new(&self.manager);
+        command.args(args);
+        command.current_dir(&self.projectpath);
+
+        run_command(command, &self.manager)
+    }
+
+    /// create the in-project virtual environment with `poetry env use`
+    fn poetry_env(&self) -> Result<(), String> {
+        self.manage(&["config", "virtualenvs.in-project", "true", "--local"])?;
+        self.manage(&["env", "use", self.python.as_str()])
+    }
+
+    /// create the in-project virtual environment with `pdm venv create`, and select it; pdm
+    /// doesn't install pip by default, which installs the development dependencies
+    fn pdm_env(&self) -> Result<(), String> {
+        self.manage(&["venv", "create", "--with-pip", self.python.as_str()])?;
+        self.manage(&["use", "--venv", "in-project"])
+    }
+
+    /// Returns `true` if the virtual environment is already exists; poetry is asked for
+    /// the environment of the project, instead of checking `pyvenv.cfg`
+    fn exists(&self) -> Result<bool, String> {
+        if self.manager == "poetry" {
+            return match std::process::Command::new("poetry")
+                .args(["env", "info", "--path"])
+                .current_dir(&self.projectpath)
+                .stderr(std::process::Stdio::null())
+                .output()
+            {
+                Ok(output) => Ok(output.status.success() && !output.stdout.trim_ascii().is_empty()),
+                Err(_) => Err(String::from("command not found: 'poetry'")),
+            };
+        }
+
+        self.path
+            .join("pyvenv.cfg")
+            .try_exists()
+            .map_err(|e| e.to_string())
+    }
+
+    /// install `self.packages` into the virtual environment
+    fn install(&self) -> Result<(), String> {
+        if self.packages.is_empty() {
+            return Ok(());
+        }
+
+        let python = venv_python(&self.path);
+
+        println!(
+            "│   {} - {} -m pip install {}",
+            "Executing".purple(),
+            python.display(),
+            self.packages.join(" ")
+        );
+
+        let mut command = std::process::Command::new(&python);
+        command.args(["-m", "pip", "install", "--quiet"]);
+        command.args(&self.packages);
+
+        run_command(command, &python.display().to_string())
+    }
+
+    fn create(&self) -> Result<(), String> {
+        if self.manager == "poetry" {
+            self.poetry_env()?;
+        } else if self.manager == "pdm" {
+            self.pdm_env()?;
+        } else if self.manager == "hatch" {
+            // the environment path is configured in `pyproject.toml`
+            self.manage(&["env", "create"])?;
+        } else if self.script == "venv" {
+            self.venv()?;
+        } else if self.script == "virtualenv" {
+            self.virtualenv()?;
+        } else if self.script == "native" {
+            self.native()?;
+        } else {
+            return Err(format!("not supported script: '{}'", self.script));
+        }
+
+        self.install()
+    }
+}
+
+impl ReInitializer for VirtualEnvironment {
+    fn initialize(&self) -> ReInitializerResult {
+        match self.exists() {
+            Ok(exists) => {
+                if exists {
+                    return ReInitializerResult::Warning(String::from(
+                        "virtual environment is already exists",
+                    ));
+                }
+            }
+            Err(e) => {
+                return ReInitializerResult::Err(e);
+            }
+        }
+
+        match self.create() {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        let remove: &[&str] = match self.manager.as_str() {
+            "poetry" => &["env", "remove", "--all"],
+            "pdm" => &["venv", "remove", "--yes", "in-project"],
+            "hatch" => &["env", "remove", "default"],
+            _ => &[],
+        };
+
+        if !remove.is_empty() {
+            if let Err(e) = self.manage(remove) {
+                return ReInitializerResult::Err(e);
+            }
+        }
+
+        match self.create() {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl Maker for VirtualEnvironment {
+    fn name(&self) -> &'static str {
+        "venv"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating virtual environment ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        // project managers read `pyproject.toml`
+        if self.manager == "pip" {
+            &["project"]
+        } else {
+            &["project", "metadata"]
+        }
+    }
+}
+
+/// Returns `initialize` warning of file makers, if `path` is already exists
+fn check_file(path: &Path) -> Option<ReInitializerResult> {
+    match path.try_exists() {
+        Ok(true) => Some(ReInitializerResult::Warning(format!(
+            "file is already exists: {}",
+            path.display()
+        ))),
+        Ok(false) => None,
+        Err(e) => Some(ReInitializerResult::Err(e.to_string())),
+    }
+}
+
+/// Write `content` into `path`
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    println!("│   Creating file: {}", path.display());
+
+    std::fs::write(path, content).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+}
+
+/// Returns the value of `git config --get <key>`, if it's set
+fn git_config_value(key: &str) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["config", "--get", key])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if output.status.success() && !value.is_empty() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Run `command` and wait for it; stdout is discarded and stderr is used as the error message
+pub(crate) fn run_command(mut command: std::process::Command, program: &str) -> Result<(), String> {
+    command.stdout(std::process::Stdio::null());
+    command.stderr(std::process::Stdio::piped());
+
+    let child = match command.spawn() {
+        Ok(o) => o,
+        Err(_) => {
+            return Err(format!("command not found: '{}'", program));
+        }
+    };
+
+    let status = child.wait_with_output().unwrap();
+
+    if !status.status.success() {
+        return Err(format!(
+            "{} [exit with {}]",
+            String::from_utf8_lossy(&status.stderr),
+            status.status.code().unwrap_or(256)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run `command` and wait for it; returns stdout, and stderr is used as the error message
+pub(crate) fn command_output(
+    mut command: std::process::Command,
+    program: &str,
+) -> Result<String, String> {
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+
+    let output = match command.output() {
+        Ok(o) => o,
+        Err(_) => {
+            return Err(format!("command not found: '{}'", program));
+        }
+    };
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} [exit with {}]",
+            String::from_utf8_lossy(&output.stderr),
+            output.status.code().unwrap_or(256)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Python interpreter of the virtual environment in `path`
+fn venv_python(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        path.join("Scripts").join("python.exe")
+    } else {
+        path.join("bin").join("python")
+    }
+}
+
+/// Returns `path` as an absolute path; `.` and `..` components are resolved lexically
+fn normalized(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut result = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                result.pop();
+            }
+            other => result.push(other),
+        }
+    }
+
+    result
+}
+
+/// Returns the virtual environment directory relative to the project directory, e.g. `venv`;
+/// `None` if it's disabled or outside of the project. An empty path is the project itself.
+fn venv_in_project(value: &arguments::Arguments) -> Option<PathBuf> {
+    let v_args = value.venv.as_ref()?;
+
+    // project managers create `.venv` in the project
+    if !v_args.manager.is_empty() && v_args.manager != "pip" {
+        return Some(PathBuf::from(&v_args.path));
+    }
+
+    let project = if value.project.name.is_empty() {
+        normalized(Path::new("."))
+    } else {
+        normalized(Path::new(&value.project.name))
+    };
+
+    let venv = if v_args.path.is_empty() {
+        normalized(Path::new("."))
+    } else {
+        normalized(Path::new(&v_args.path))
+    };
+
+    venv.strip_prefix(&project).ok().map(Path::to_path_buf)
+}
+
+/// Development dependencies of the project; e.g. test framework
+fn dev_dependencies(value: &arguments::Arguments) -> Vec<String> {
+    let mut result = Vec::new();
+
+    if value.tools.tests == "pytest" {
+        result.push(String::from("pytest"));
+    }
+
+    match value.tools.lint.as_str() {
+        "ruff" => result.push(String::from("ruff")),
+        "black+isort" => result.extend([String::from("black"), String::from("isort")]),
+        "flake8" => result.push(String::from("flake8")),
+        _ => (),
+    }
+
+    if !value.tools.typecheck.is_empty() {
+        result.push(value.tools.typecheck.clone());
+    }
+
+    if value.tools.pre_commit {
+        result.push(String::from("pre-commit"));
+    }
+
+    result
+}
+
+/// Line length of linters and formatters
+const LINE_LENGTH: usize = 88;
+
+/// Returns the `pyXY` tag of `version`, e.g. `py312` for `3.12`
+fn python_tag(version: &str) -> Option<String> {
+    let mut pieces = version.split('.');
+    let major: u32 = pieces.next()?.parse().ok()?;
+    let minor: u32 = pieces.next()?.parse().ok()?;
+
+    Some(format!("py{}{}", major, minor))
+}
+
+/// Returns the current year (UTC)
+fn current_year() -> i64 {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    // days to civil date; see http://howardhinnant.github.io/date_algorithms.html
+    let z = seconds.div_euclid(86400) + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    yoe + era * 400 + i64::from(month <= 2)
+}
+
+/// License maker, writes `LICENSE` file
+#[derive(Debug)]
+pub struct License {
+    id: String,
+    author: String,
+    year: i64,
+
+    /// Directory which `LICENSE` is written in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<arguments::ProjectArguments> for License {
+    type Error = String;
+
+    fn try_from(value: arguments::ProjectArguments) -> Result<Self, Self::Error> {
+        if template::license(&value.license).is_none() {
+            return Err(format!(
+                "not supported license: '{}' (supported licenses: {})",
+                value.license,
+                template::LICENSES.map(|(id, _)| id).join(", ")
+            ));
+        }
+
+        if value.author.is_empty() {
+            return Err(String::from(
+                "license author is unknown; use '--author' or set git 'user.name'",
+            ));
+        }
+
+        Ok(License {
+            id: value.license,
+            author: value.author,
+            year: current_year(),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl License {
+    fn write(&self) -> ReInitializerResult {
+        let text = template::render(
+            template::license(&self.id).unwrap(),
+            &[("year", &self.year.to_string()), ("author", &self.author)],
+        );
+
+        match write_file(&self.projectpath.join("LICENSE"), &text) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for License {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.projectpath.join("LICENSE")).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for License {
+    fn name(&self) -> &'static str {
+        "license"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating license ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Metadata maker, writes `pyproject.toml` packaging metadata
+#[derive(Debug)]
+pub struct Metadata {
+    name: String,
+    package: String,
+    author: String,
+    email: String,
+    license: String,
+    description: String,
+    python: String,
+    layout: String,
+    readme: String,
+    tests: String,
+    lint: String,
+    typecheck: String,
+    strict: bool,
+    manager: String,
+    dev_dependencies: Vec<String>,
+
+    /// `requires-python` specifier; `>=<python>`, unless it's copied from the root project
+    requires_python: String,
+
+    /// Directory which `pyproject.toml` is written in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Metadata {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        let name = project_name(&value.project);
+
+        Ok(Metadata {
+            package: package_name(&name),
+            name,
+            author: value.project.author.clone(),
+            email: value.project.email.clone(),
+            license: value.project.license.clone(),
+            description: value.project.description.clone(),
+            python: value.project.python.clone(),
+            layout: value.project.layout.clone(),
+            readme: readme_file(&value.project.readme_format),
+            tests: value.tools.tests.clone(),
+            lint: value.tools.lint.clone(),
+            typecheck: value.tools.typecheck.clone(),
+            strict: value.tools.strictness == "strict",
+            manager: value
+                .venv
+                .as_ref()
+                .map(|v| v.manager.clone())
+                .unwrap_or_default(),
+            dev_dependencies: dev_dependencies(value),
+            requires_python: format!(">={}", value.project.python),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Metadata {
+    /// Render `pyproject.toml` content
+    fn render(&self) -> String {
+        let mut project = format!(
+            "[project]\nname = {}\nversion = \"0.1.0\"\n",
+            toml_string(&self.name)
+        );
+
+        if !self.description.is_empty() {
+            project.push_str(&format!(
+                "description = {}\n",
+                toml_string(&self.description)
+            ));
+        }
+
+        project.push_str(&format!(
+            "readme = {}\nrequires-python = {}\n",
+            toml_string(&self.readme),
+            toml_string(&self.requires_python)
+        ));
+
+        if !self.license.is_empty() {
+            // Proprietary is not a SPDX identifier; PEP 639 uses LicenseRef- for custom licenses
+            let expression = if self.license == "Proprietary" {
+                String::from("LicenseRef-Proprietary")
+            } else {
+                self.license.clone()
+            };
+
+            project.push_str(&format!(
+                "license = {}\nlicense-files = [\"LICENSE\"]\n",
+                toml_string(&expression)
+            ));
+        }
+
+        if !self.author.is_empty() {
+            if self.email.is_empty() {
+                project.push_str(&format!(
+                    "authors = [{{ name = {} }}]\n",
+                    toml_string(&self.author)
+                ));
+            } else {
+                project.push_str(&format!(
+                    "authors = [{{ name = {}, email = {} }}]\n",
+                    toml_string(&self.author),
+                    toml_string(&self.email)
+                ));
+            }
+        }
+
+        if !self.dev_dependencies.is_empty() {
+            project.push_str(&format!(
+                "\n[project.optional-dependencies]\ndev = [{}]\n",
+                quoted_list(&self.dev_dependencies)
+            ));
+        }
+
+        let (build_system, mut tools) = if self.manager == "poetry" {
+            let from = if self.layout == "flat" {
+                ""
+            } else {
+                ", from = \"src\""
+            };
+
+            (
+                "requires = [\"poetry-core>=2.0.0,<3.0.0\"]\nbuild-backend = \"poetry.core.masonry.api\"",
+                format!(
+                    "[tool.poetry]\npackages = [{{ include = {}{} }}]\n",
+                    toml_string(&self.package),
+                    from
+                ),
+            )
+        } else if self.manager == "pdm" {
+            // pdm-backend finds the package in both layouts
+            (
+                "requires = [\"pdm-backend\"]\nbuild-backend = \"pdm.backend\"",
+                String::from("[tool.pdm]\ndistribution = true\n"),
+            )
+        } else if self.manager == "hatch" {
+            let package = if self.layout == "flat" {
+                self.package.clone()
+            } else {
+                format!("src/{}", self.package)
+            };
+
+            let mut tools = format!(
+                "[tool.hatch.build.targets.wheel]\npackages = [{}]\n\n[tool.hatch.envs.default]\ntype = \"virtual\"\npath = \".venv\"\npython = {}\n",
+                toml_string(&package),
+                toml_string(&self.python)
+            );
+
+            if !self.dev_dependencies.is_empty() {
+                tools.push_str("features = [\"dev\"]\n");
+            }
+
+            (
+                "requires = [\"hatchling\"]\nbuild-backend = \"hatchling.build\"",
+                tools,
+            )
+        } else if self.layout == "flat" {
+            (
+                "requires = [\"setuptools>=77\"]\nbuild-backend = \"setuptools.build_meta\"",
+                format!(
+                    "[tool.setuptools]\npackages = [{}]\n",
+                    toml_string(&self.package)
+                ),
+            )
+        } else {
+            (
+                "requires = [\"setuptools>=77\"]\nbuild-backend = \"setuptools.build_meta\"",
+                String::from("[tool.setuptools.packages.find]\nwhere = [\"src\"]\n"),
+            )
+        };
+
+        if self.tests == "pytest" {
+            tools.push_str("\n[tool.pytest.ini_options]\ntestpaths = [\"tests\"]\n");
+
+            if self.layout != "flat" {
+                tools.push_str("pythonpath = [\"src\"]\n");
+            }
+        }
+
+        let tag = python_tag(&self.python);
+
+        if self.lint == "ruff" {
+            tools.push_str(&format!("\n[tool.ruff]\nline-length = {}\n", LINE_LENGTH));
+            if let Some(tag) = &tag {
+                tools.push_str(&format!("target-version = {}\n", toml_string(tag)));
+            }
+            tools.push_str(
+                "\n[tool.ruff.lint]\nselect = [\"E\", \"F\", \"W\", \"I\", \"B\", \"UP\"]\n",
+            );
+        } else if self.lint == "black+isort" {
+            tools.push_str(&format!("\n[tool.black]\nline-length = {}\n", LINE_LENGTH));
+            if let Some(tag) = &tag {
+                tools.push_str(&format!("target-version = [{}]\n", toml_string(tag)));
+            }
+            tools.push_str(&format!(
+                "\n[tool.isort]\nprofile = \"black\"\nline_length = {}\n",
+                LINE_LENGTH
+            ));
+            if let Some(tag) = &tag {
+                tools.push_str(&format!("py_version = {}\n", &tag[2..]));
+            }
+        }
+
+        if self.typecheck == "mypy" {
+            tools.push_str("\n[tool.mypy]\n");
+            if tag.is_some() {
+                tools.push_str(&format!("python_version = {}\n", toml_string(&self.python)));
+            }
+            tools.push_str(&format!("packages = [{}]\n", toml_string(&self.package)));
+            if self.layout != "flat" {
+                tools.push_str("mypy_path = \"src\"\n");
+            }
+            if self.strict {
+                tools.push_str("strict = true\n");
+            } else {
+                tools.push_str("check_untyped_defs = true\nwarn_unused_configs = true\n");
+            }
+        }
+
+        format!("[build-system]\n{}\n\n{}\n{}", build_system, project, tools)
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        match write_file(&self.projectpath.join("pyproject.toml"), &self.render()) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Metadata {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.projectpath.join("pyproject.toml")).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Metadata {
+    fn name(&self) -> &'static str {
+        "metadata"
+    }
+
+    fn title(&self) -> &'static str {
+        "Writing project metadata ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Returns `X.Y` version of `python` interpreter
+fn python_version(python: &str) -> Option<String> {
+    let output = std::process::Command::new(python)
+        .args(["-c", "import sys; print('%d.%d' % sys.version_info[:2])"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if output.status.success() && !version.is_empty() {
+        Some(version)
+    } else {
+        None
+    }
+}
+
+/// Package maker, creates the python package directory
+#[derive(Debug)]
+pub struct Package {
+    package: String,
+    description: String,
+    layout: String,
+
+    /// Add `py.typed` marker
+    typed: bool,
+
+    /// Project directory; the package is created in it, or in its `src` directory
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Package {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        let package = package_name(&project_name(&value.project));
+
+        Ok(Package {
+            package,
+            description: value.project.description.clone(),
+            layout: value.project.layout.clone(),
+            typed: !value.tools.typecheck.is_empty(),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Package {
+    /// Package directory, depends on the layout
+    fn directory(&self) -> PathBuf {
+        if self.layout == "flat" {
+            self.projectpath.join(&self.package)
+        } else {
+            self.projectpath.join("src").join(&self.package)
+        }
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        let directory = self.directory();
+
+        println!("│   Creating directory: {}", directory.display());
+        if let Err(e) = std::fs::create_dir_all(&directory) {
+            return ReInitializerResult::Err(format!(
+                "cannot create {}: {}",
+                directory.display(),
+                e
+            ));
+        }
+
+        let mut content = String::new();
+        if !self.description.is_empty() {
+            content.push_str(&format!("{}\n\n", python_docstring(&self.description)));
+        }
+        content.push_str("__version__ = \"0.1.0\"\n");
+
+        let mut result = write_file(&directory.join("__init__.py"), &content);
+
+        if self.typed {
+            result = result.and_then(|_| write_file(&directory.join("py.typed"), ""));
+        }
+
+        match result {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Package {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.directory().join("__init__.py")).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Package {
+    fn name(&self) -> &'static str {
+        "package"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating package ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// README maker, writes `README.md` or `README.rst`
+#[derive(Debug)]
+pub struct Readme {
+    name: String,
+    package: String,
+    description: String,
+    license: String,
+    python: String,
+    layout: String,
+    format: String,
+
+    /// Virtual environment script and path, if it's enabled
+    venv: Option<arguments::VirtualEnvironmentArguments>,
+
+    /// Python interpreter of the setup instructions, e.g. `python3.12`
+    interpreter: String,
+
+    /// Directory which the readme file is written in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Readme {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        let name = project_name(&value.project);
+
+        Ok(Readme {
+            package: package_name(&name),
+            name,
+            description: value.project.description.clone(),
+            license: value.project.license.clone(),
+            python: value.project.python.clone(),
+            layout: value.project.layout.clone(),
+            format: value.project.readme_format.clone(),
+            venv: value.venv.clone().map(|mut v| {
+                // the instructions are run in the project directory
+                v.path = match venv_in_project(value) {
+                    Some(path) => path.display().to_string(),
+                    None => String::from("venv"),
+                };
+                v
+            }),
+            interpreter: String::from("python3"),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Readme {
+    /// shields.io badge url; `-` have to be escaped in badge texts
+    fn badge(label: &str, message: &str) -> String {
+        format!(
+            "https://img.shields.io/badge/{}-{}-blue.svg",
+            label,
+            message.replace('-', "--").replace('+', "%2B")
+        )
+    }
+
+    /// Shell commands which install the project
+    fn install_commands(&self) -> Vec<String> {
+        let mut commands = Vec::new();
+
+        if let Some(venv) = self.venv.as_ref().filter(|v| v.manager == "poetry") {
+            commands.push(format!("poetry env use {}", self.interpreter));
+            commands.push(String::from("poetry install"));
+            commands.push(format!("source {}/bin/activate", venv.path));
+            return commands;
+        }
+
+        if let Some(venv) = self.venv.as_ref().filter(|v| v.manager == "pdm") {
+            commands.push(format!("pdm venv create {}", self.interpreter));
+            commands.push(String::from("pdm install"));
+            commands.push(format!("source {}/bin/activate", venv.path));
+            return commands;
+        }
+
+        if self.venv.as_ref().is_some_and(|v| v.manager == "hatch") {
+            commands.push(String::from("hatch env create"));
+            commands.push(String::from("hatch shell"));
+            return commands;
+        }
+
+        if let Some(venv) = &self.venv {
+            let path = if venv.path.is_empty() {
+                "."
+            } else {
+                venv.path.as_str()
+            };
+
+            if venv.script == "virtualenv" {
+                commands.push(format!("virtualenv -p {} {}", self.interpreter, path));
+            } else {
+                commands.push(format!("{} -m venv {}", self.interpreter, path));
+            }
+
+            commands.push(format!("source {}/bin/activate", path));
+        }
+
+        commands.push(String::from("pip install -e ."));
+        commands
+    }
+
+    fn render(&self) -> String {
+        let mut badges = vec![(
+            "python",
+            Readme::badge("python", &format!("{}+", self.python)),
+        )];
+
+        if !self.license.is_empty() {
+            badges.push(("license", Readme::badge("license", &self.license)));
+        }
+
+        let location = if self.layout == "flat" {
+            format!("{}/", self.package)
+        } else {
+            format!("src/{}/", self.package)
+        };
+
+        let commands = self.install_commands();
+
+        let (text, title, badges, install) = if self.format == "rst" {
+            (
+                include_str!("templates/README.rst"),
+                format!("{}\n{}", self.name, "=".repeat(self.name.chars().count())),
+                badges
+                    .iter()
+                    .map(|(alt, url)| format!(".. image:: {}\n   :alt: {}", url, alt))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                format!(
+                    "The package lives in ``{}``; install it in editable mode:\n\n.. code-block:: bash\n\n{}\n",
+                    location,
+                    commands.iter().map(|c| format!("   {}", c)).collect::<Vec<String>>().join("\n")
+                ),
+            )
+        } else {
+            (
+                include_str!("templates/README.md"),
+                self.name.clone(),
+                badges
+                    .iter()
+                    .map(|(alt, url)| format!("![{}]({})", alt, url))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                format!(
+                    "The package lives in `{}`; install it in editable mode:\n```bash\n{}\n```\n",
+                    location,
+                    commands.join("\n")
+                ),
+            )
+        };
+
+        let mut content = template::render(
+            text,
+            &[
+                ("name", &self.name),
+                ("title", &title),
+                ("badges", &badges),
+                ("description", &self.description),
+                ("install", install.trim_end()),
+            ],
+        );
+
+        // an empty description leaves extra blank lines
+        while content.contains("\n\n\n") {
+            content = content.replace("\n\n\n", "\n\n");
+        }
+
+        content
+    }
+
+    fn path(&self) -> PathBuf {
+        self.projectpath.join(readme_file(&self.format))
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        match write_file(&self.path(), &self.render()) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Readme {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.path()).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Readme {
+    fn name(&self) -> &'static str {
+        "readme"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating README ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Lint maker, writes linter configuration files which can't be set in `pyproject.toml`
+#[derive(Debug)]
+pub struct Lint {
+    excludes: Vec<String>,
+
+    /// Directory which the configuration files are written in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Lint {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        if value.tools.lint != "flake8" {
+            return Err(format!(
+                "linter preset doesn't need a configuration file: '{}'",
+                value.tools.lint
+            ));
+        }
+
+        let mut excludes = vec![String::from(".git")];
+
+        // a virtual environment in the project directory itself can't be excluded
+        if let Some(venv) = venv_in_project(value).filter(|v| !v.as_os_str().is_empty()) {
+            excludes.push(venv.display().to_string());
+        }
+
+        Ok(Lint {
+            excludes,
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Lint {
+    fn path(&self) -> PathBuf {
+        self.projectpath.join(".flake8")
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        let content = format!(
+            "[flake8]\nmax-line-length = {}\nextend-exclude = {}\n",
+            LINE_LENGTH,
+            self.excludes.join(",")
+        );
+
+        match write_file(&self.path(), &content) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Lint {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.path()).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Lint {
+    fn name(&self) -> &'static str {
+        "lint"
+    }
+
+    fn title(&self) -> &'static str {
+        "Configuring linters ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Type checker maker, writes `pyrightconfig.json`; mypy is configured in `pyproject.toml`
+#[derive(Debug)]
+pub struct TypeCheck {
+    include: String,
+    python: String,
+    strictness: String,
+
+    /// Virtual environment directory which the venv maker uses, e.g. `<project>/venv`; `None`
+    /// if the venv step isn't enabled
+    venv: Option<PathBuf>,
+
+    /// Directory which `pyrightconfig.json` is written in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for TypeCheck {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        if value.tools.typecheck != "pyright" {
+            return Err(format!(
+                "type checker doesn't need a configuration file: '{}'",
+                value.tools.typecheck
+            ));
+        }
+
+        let include = if value.project.layout == "flat" {
+            package_name(&project_name(&value.project))
+        } else {
+            String::from("src")
+        };
+
+        Ok(TypeCheck {
+            include,
+            python: value.project.python.clone(),
+            strictness: value.tools.strictness.clone(),
+            venv: None,
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl TypeCheck {
+    fn path(&self) -> PathBuf {
+        self.projectpath.join("pyrightconfig.json")
+    }
+
+    fn render(&self) -> String {
+        let mut lines = vec![format!("  \"include\": [{:?}]", self.include)];
+
+        if python_tag(&self.python).is_some() {
+            lines.push(format!("  \"pythonVersion\": {:?}", self.python));
+        }
+
+        lines.push(format!("  \"typeCheckingMode\": {:?}", self.strictness));
+
+        // pyright finds the environment as `venvPath/venv`, relative to the project
+        if let Some(venv) = &self.venv {
+            let venv = normalized(venv);
+
+            // a virtual environment outside of the project is given by its absolute path
+            let relative = venv
+                .strip_prefix(normalized(&self.projectpath))
+                .unwrap_or(&venv);
+
+            let (parent, name) = if relative.as_os_str().is_empty() {
+                // the project directory itself is the virtual environment
+                let project = std::path::absolute(&self.projectpath)
+                    .unwrap_or_else(|_| self.projectpath.clone());
+
+                (
+                    String::from(".."),
+                    project.file_name().map(|n| n.to_string_lossy().to_string()),
+                )
+            } else {
+                let parent = match relative.parent().map(|p| p.display().to_string()) {
+                    Some(p) if !p.is_empty() => p,
+                    _ => String::from("."),
+                };
+
+                (
+                    parent,
+                    relative
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string()),
+                )
+            };
+
+            if let Some(name) = name {
+                lines.push(format!("  \"venvPath\": {:?}", parent));
+                lines.push(format!("  \"venv\": {:?}", name));
+            }
+        }
+
+        format!("{{\n{}\n}}\n", lines.join(",\n"))
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        match write_file(&self.path(), &self.render()) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for TypeCheck {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.path()).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for TypeCheck {
+    fn name(&self) -> &'static str {
+        "typecheck"
+    }
+
+    fn title(&self) -> &'static str {
+        "Configuring type checker ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// pre-commit maker, writes `.pre-commit-config.yaml` and installs the git hooks
+#[derive(Debug)]
+pub struct PreCommit {
+    repos: Vec<&'static str>,
+
+    /// Virtual environment which pre-commit is installed in
+    venvpath: PathBuf,
+
+    /// Git repository which the hooks are installed in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for PreCommit {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        if value.git.is_none() || value.venv.is_none() {
+            return Err(String::from(
+                "'--pre-commit' requires git ('-g') and the virtual environment",
+            ));
+        }
+
+        let repos = template::PRE_COMMIT_REPOS
+            .iter()
+            .filter(|(name, _)| {
+                *name == "base" || *name == value.tools.lint || *name == value.tools.typecheck
+            })
+            .map(|(_, text)| *text)
+            .collect();
+
+        Ok(PreCommit {
+            repos,
+            venvpath: PathBuf::new(),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl PreCommit {
+    fn path(&self) -> PathBuf {
+        self.projectpath.join(".pre-commit-config.yaml")
+    }
+
+    /// perform `pre-commit install` command in the project repository
+    fn install(&self) -> Result<(), String> {
+        let python = venv_python(&self.venvpath);
+        let program = python.with_file_name(if cfg!(windows) {
+            "pre-commit.exe"
+        } else {
+            "pre-commit"
+        });
+        let program = std::path::absolute(&program).unwrap_or(program);
+
+        println!(
+            "│   {} - {} install",
+            "Executing".purple(),
+            program.display()
+        );
+
+        let mut command = std::process::Command::new(&program);
+        command.arg("install");
+        command.current_dir(&self.projectpath);
+
+        run_command(command, &program.display().to_string())
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        match write_file(&self.path(), &self.repos.concat()).and_then(|_| self.install()) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for PreCommit {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.path()).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for PreCommit {
+    fn name(&self) -> &'static str {
+        "pre-commit"
+    }
+
+    fn title(&self) -> &'static str {
+        "Installing pre-commit hooks ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["git", "venv"]
+    }
+}
+
+/// Returns the CI service of a git remote URL, e.g. `github` for `git@github.com:org/repo.git`
+fn ci_service(remote_url: &str) -> Option<&'static str> {
+    let address = match remote_url.split_once("://") {
+        Some((_, rest)) => rest,
+        None => remote_url,
+    };
+
+    let address = match address.split_once('@') {
+        Some((_, rest)) => rest,
+        None => address,
+    };
+
+    let host = address
+        .split([':', '/'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    if host == "github.com" {
+        Some("github")
+    } else if host.starts_with("gitlab.") || host.contains(".gitlab.") {
+        Some("gitlab")
+    } else if host.contains("gitea") || host == "codeberg.org" {
+        Some("gitea")
+    } else {
+        None
+    }
+}
+
+/// CI maker, writes the CI workflow which installs, lints, tests and builds the project
+#[derive(Debug)]
+pub struct Ci {
+    service: String,
+    python_versions: Vec<String>,
+
+    /// Workflow steps; pairs of step name and shell commands
+    steps: Vec<(&'static str, Vec<String>)>,
+
+    /// Project directory; the workflow file is written under it, e.g. `.github/workflows`
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Ci {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        let service = if value.tools.ci == "auto" {
+            let mut urls: Vec<String> = Vec::new();
+
+            if let Some(g) = &value.git {
+                urls.push(g.remote_url.clone());
+                urls.extend(
+                    g.remotes
+                        .iter()
+                        .filter_map(|entry| entry.split_once('=').map(|(_, url)| url.trim()))
+                        .map(String::from),
+                );
+            }
+
+            // in place, the remotes of the existing repository are used, too
+            if value.project.in_place {
+                let directory = if value.project.name.is_empty() {
+                    Path::new(".")
+                } else {
+                    Path::new(&value.project.name)
+                };
+
+                urls.extend(git::backend().remote_urls(directory));
+            }
+
+            // the first remote which is hosted by a known service, e.g. a GitHub mirror
+            let service = urls.iter().find_map(|url| ci_service(url));
+
+            match service {
+                Some(service) => String::from(service),
+                None => {
+                    return Err(String::from(
+                        "cannot infer CI service from the git remote URLs; use '--ci github|gitlab|gitea'",
+                    ));
+                }
+            }
+        } else {
+            value.tools.ci.clone()
+        };
+
+        if !["github", "gitlab", "gitea"].contains(&service.as_str()) {
+            return Err(format!("not supported CI service: '{}'", service));
+        }
+
+        let python_versions = if value.tools.ci_python.is_empty() {
+            vec![value.project.python.clone()]
+        } else {
+            value.tools.ci_python.clone()
+        };
+
+        let install = if dev_dependencies(value).is_empty() {
+            "pip install -e ."
+        } else {
+            "pip install -e \".[dev]\""
+        };
+
+        let mut steps: Vec<(&'static str, Vec<String>)> = vec![(
+            "Install",
+            vec![
+                String::from("python -m pip install --upgrade pip"),
+                String::from(install),
+            ],
+        )];
+
+        let mut lint: Vec<&str> = match value.tools.lint.as_str() {
+            "ruff" => vec!["ruff check .", "ruff format --check ."],
+            "black+isort" => vec!["black --check .", "isort --check-only ."],
+            "flake8" => vec!["flake8"],
+            _ => Vec::new(),
+        };
+
+        match value.tools.typecheck.as_str() {
+            "mypy" => lint.push("mypy"),
+            "pyright" => lint.push("pyright"),
+            _ => (),
+        }
+
+        if !lint.is_empty() {
+            steps.push(("Lint", lint.into_iter().map(String::from).collect()));
+        }
+
+        match value.tools.tests.as_str() {
+            "pytest" => steps.push(("Test", vec![String::from("pytest")])),
+            "unittest" => steps.push((
+                "Test",
+                vec![String::from("python -m unittest discover -s tests -t .")],
+            )),
+            _ => (),
+        }
+
+        steps.push((
+            "Build wheel",
+            vec![
+                String::from("pip install build"),
+                String::from("python -m build --wheel"),
+            ],
+        ));
+
+        Ok(Ci {
+            service,
+            python_versions,
+            steps,
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Ci {
+    fn path(&self) -> PathBuf {
+        match self.service.as_str() {
+            "gitlab" => self.projectpath.join(".gitlab-ci.yml"),
+            "gitea" => self
+                .projectpath
+                .join(".gitea")
+                .join("workflows")
+                .join("ci.yml"),
+            _ => self
+                .projectpath
+                .join(".github")
+                .join("workflows")
+                .join("ci.yml"),
+        }
+    }
+
+    fn render(&self) -> String {
+        let versions = quoted_list(&self.python_versions);
+
+        if self.service == "gitlab" {
+            let commands: Vec<String> = self
+                .steps
+                .iter()
+                .flat_map(|(_, commands)| commands.iter().map(|c| format!("    - {}", c)))
+                .collect();
+
+            return template::render(
+                include_str!("templates/ci/gitlab.yml"),
+                &[
+                    ("python_versions", &versions),
+                    ("commands", &commands.join("\n")),
+                ],
+            );
+        }
+
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .map(|(name, commands)| {
+                format!(
+                    "      - name: {}\n        run: |\n{}",
+                    name,
+                    commands
+                        .iter()
+                        .map(|c| format!("          {}", c))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                )
+            })
+            .collect();
+
+        // gitea actions are compatible with github actions
+        template::render(
+            include_str!("templates/ci/github.yml"),
+            &[("python_versions", &versions), ("steps", &steps.join("\n"))],
+        )
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        let path = self.path();
+
+        if let Some(directory) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(directory) {
+                return ReInitializerResult::Err(format!(
+                    "cannot create {}: {}",
+                    directory.display(),
+                    e
+                ));
+            }
+        }
+
+        match write_file(&path, &self.render()) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Ci {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.path()).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Ci {
+    fn name(&self) -> &'static str {
+        "ci"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating CI workflow ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Docker maker, writes `Dockerfile`, `.dockerignore` and optionally `compose.yaml`
+#[derive(Debug)]
+pub struct Docker {
+    tool: String,
+    python: String,
+    package: String,
+
+    /// Compose service name, if `compose.yaml` is enabled
+    service: Option<String>,
+
+    /// Paths which are excluded from the build context
+    ignores: Vec<String>,
+
+    /// Directory of the build context
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Docker {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        if !["pip", "uv", "poetry"].contains(&value.tools.docker.as_str()) {
+            return Err(format!(
+                "not supported dependency tool for Dockerfile: '{}'",
+                value.tools.docker
+            ));
+        }
+
+        let name = project_name(&value.project);
+
+        let mut ignores = vec![String::from(".git")];
+
+        // a virtual environment in the project directory itself can't be ignored
+        if let Some(venv) = venv_in_project(value).filter(|v| !v.as_os_str().is_empty()) {
+            ignores.push(venv.display().to_string());
+        }
+
+        ignores.extend(
+            [
+                "__pycache__",
+                "*.py[cod]",
+                ".pytest_cache",
+                ".mypy_cache",
+                ".ruff_cache",
+                "build",
+                "dist",
+                "*.egg-info",
+            ]
+            .map(String::from),
+        );
+
+        // compose service names can contain lowercase letters, digits, '-' and '_'
+        let service = value.tools.compose.then(|| {
+            name.to_lowercase()
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        c
+                    } else {
+                        '-'
+                    }
+                })
+                .collect()
+        });
+
+        Ok(Docker {
+            tool: value.tools.docker.clone(),
+            python: value.project.python.clone(),
+            package: package_name(&name),
+            service,
+            ignores,
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Docker {
+    fn dockerfile(&self) -> String {
+        let text = match self.tool.as_str() {
+            "uv" => include_str!("templates/docker/Dockerfile.uv"),
+            "poetry" => include_str!("templates/docker/Dockerfile.poetry"),
+            _ => include_str!("templates/docker/Dockerfile.pip"),
+        };
+
+        template::render(
+            text,
+            &[("python", &self.python), ("package", &self.package)],
+        )
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        let mut result = write_file(&self.projectpath.join("Dockerfile"), &self.dockerfile())
+            .and_then(|_| {
+                write_file(
+                    &self.projectpath.join(".dockerignore"),
+                    &format!("{}\n", self.ignores.join("\n")),
+                )
+            });
+
+        if let Some(service) = &self.service {
+            result = result.and_then(|_| {
+                write_file(
+                    &self.projectpath.join("compose.yaml"),
+                    &template::render(
+                        include_str!("templates/docker/compose.yaml"),
+                        &[("service", service)],
+                    ),
+                )
+            });
+        }
+
+        match result {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Docker {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.projectpath.join("Dockerfile")).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Docker {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating Dockerfile ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Tests maker, creates the `tests` package with a smoke test
+#[derive(Debug)]
+pub struct Tests {
+    framework: String,
+    package: String,
+
+    /// Directory which the `tests` package is created in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Tests {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        if value.tools.tests != "pytest" && value.tools.tests != "unittest" {
+            return Err(format!(
+                "not supported test framework: '{}'",
+                value.tools.tests
+            ));
+        }
+
+        Ok(Tests {
+            framework: value.tools.tests.clone(),
+            package: package_name(&project_name(&value.project)),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Tests {
+    fn smoke_test(&self) -> PathBuf {
+        self.projectpath.join("tests").join("test_smoke.py")
+    }
+
+    fn write(&self) -> ReInitializerResult {
+        let directory = self.projectpath.join("tests");
+
+        println!("│   Creating directory: {}", directory.display());
+        if let Err(e) = std::fs::create_dir_all(&directory) {
+            return ReInitializerResult::Err(format!(
+                "cannot create {}: {}",
+                directory.display(),
+                e
+            ));
+        }
+
+        let text = if self.framework == "pytest" {
+            include_str!("templates/tests/test_smoke_pytest.py")
+        } else {
+            include_str!("templates/tests/test_smoke_unittest.py")
+        };
+
+        let result = write_file(&directory.join("__init__.py"), "").and_then(|_| {
+            write_file(
+                &self.smoke_test(),
+                &template::render(text, &[("package", &self.package)]),
+            )
+        });
+
+        match result {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl ReInitializer for Tests {
+    fn initialize(&self) -> ReInitializerResult {
+        check_file(&self.smoke_test()).unwrap_or_else(|| self.write())
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.write()
+    }
+}
+
+impl Maker for Tests {
+    fn name(&self) -> &'static str {
+        "tests"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating tests ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project"]
+    }
+}
+
+/// Self-check maker, runs the smoke test once in the created environment
+#[derive(Debug)]
+pub struct SelfCheck {
+    framework: String,
+    layout: String,
+
+    /// Python interpreter which runs the smoke test; the virtual environment interpreter, if
+    /// it's enabled
+    python: PathBuf,
+
+    /// Directory which the smoke test is run in
+    projectpath: PathBuf,
+
+    result: RefCell<Option<String>>,
+}
+
+impl TryFrom<&arguments::Arguments> for SelfCheck {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        Ok(SelfCheck {
+            framework: value.tools.tests.clone(),
+            layout: value.project.layout.clone(),
+            python: PathBuf::from("python3"),
+            projectpath: PathBuf::new(),
+            result: RefCell::new(None),
+        })
+    }
+}
+
+impl SelfCheck {
+    fn check(&self) -> ReInitializerResult {
+        let mut command = std::process::Command::new(&self.python);
+
+        if self.framework == "pytest" {
+            command.args(["-m", "pytest", "-q"]);
+        } else {
+            command.args(["-m", "unittest", "discover", "-s", "tests", "-t", "."]);
+        }
+
+        if self.layout != "flat" {
+            command.env("PYTHONPATH", "src");
+        }
+
+        command.current_dir(&self.projectpath);
+
+        println!(
+            "│   {} - {} -m {}",
+            "Executing".purple(),
+            self.python.display(),
+            self.framework
+        );
+
+        let output = match command.output() {
+            Ok(o) => o,
+            Err(_) => {
+                return ReInitializerResult::Err(format!(
+                    "command not found: '{}'",
+                    self.python.display()
+                ));
+            }
+        };
+
+        // a failing smoke test doesn't stop createpy; it's reported in the summary
+        let result = if output.status.success() {
+            String::from("smoke test passed")
+        } else {
+            println!("│   {} smoke test failed:", "warning:".yellow());
+
+            for line in String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .chain(String::from_utf8_lossy(&output.stderr).lines())
+            {
+                println!("│       {}", line);
+            }
+
+            format!(
+                "smoke test failed [exit with {}]",
+                output.status.code().unwrap_or(256)
+            )
+        };
+
+        *self.result.borrow_mut() = Some(result);
+        ReInitializerResult::Ok
+    }
+}
+
+impl ReInitializer for SelfCheck {
+    fn initialize(&self) -> ReInitializerResult {
+        self.check()
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.check()
+    }
+}
+
+impl Maker for SelfCheck {
+    fn name(&self) -> &'static str {
+        "self-check"
+    }
+
+    fn title(&self) -> &'static str {
+        "Running smoke test ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["package", "tests", "metadata", "venv"]
+    }
+
+    fn summary(&self) -> Option<String> {
+        self.result.borrow().clone()
+    }
+}
+
+/// Lock file maker; records the dependencies which are installed in the virtual environment
+#[derive(Debug)]
+pub struct Lock {
+    /// `pip`, `uv`, `poetry` or `pdm`
+    tool: String,
+
+    /// Python interpreter which lists the installed packages; the virtual environment
+    /// interpreter, if it's enabled
+    python: PathBuf,
+
+    /// Directory which the lock file is written in
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Lock {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        let manager = match &value.venv {
+            Some(v_args) if !v_args.manager.is_empty() => v_args.manager.as_str(),
+            _ => "pip",
+        };
+
+        let has_lock = manager == "poetry" || manager == "pdm";
+
+        let tool = match value.tools.lock.as_str() {
+            "auto" if has_lock => manager,
+            "auto" => "pip",
+            "manager" if has_lock => manager,
+            "manager" => {
+                return Err(format!("project manager has no lock command: {}", manager));
+            }
+            "pip" | "uv" => value.tools.lock.as_str(),
+            other => {
+                return Err(format!("not supported lock tool: {}", other));
+            }
+        };
+
+        if tool == "pip" && value.venv.is_none() {
+            return Err(String::from(
+                "'pip freeze' lock requires the virtual environment",
+            ));
+        }
+
+        if tool == "pip" && value.venv.as_ref().is_some_and(|v| v.without_pip) {
+            return Err(String::from(
+                "'pip freeze' lock requires pip; remove '--without-pip'",
+            ));
+        }
+
+        Ok(Lock {
+            tool: String::from(tool),
+            python: PathBuf::from("python3"),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Lock {
+    fn file(&self) -> PathBuf {
+        self.projectpath.join(match self.tool.as_str() {
+            "poetry" => "poetry.lock",
+            "pdm" => "pdm.lock",
+            _ => "requirements.lock",
+        })
+    }
+
+    fn lock(&self) -> Result<(), String> {
+        match self.tool.as_str() {
+            "pip" => {
+                println!(
+                    "│   {} - {} -m pip freeze --exclude-editable",
+                    "Executing".purple(),
+                    self.python.display()
+                );
+
+                let mut command = std::process::Command::new(&self.python);
+                command.args(["-m", "pip", "freeze", "--exclude-editable"]);
+
+                let frozen = command_output(command, &self.python.display().to_string())?;
+                write_file(&self.file(), &frozen)
+            }
+            "uv" => {
+                println!(
+                    "│   {} - uv pip compile pyproject.toml --all-extras -o requirements.lock",
+                    "Executing".purple()
+                );
+
+                let mut command = std::process::Command::new("uv");
+                command.args([
+                    "pip",
+                    "compile",
+                    "pyproject.toml",
+                    "--all-extras",
+                    "--quiet",
+                ]);
+                command.arg("--python").arg(&self.python);
+                command.args(["-o", "requirements.lock"]);
+                command.current_dir(&self.projectpath);
+
+                run_command(command, "uv")
+            }
+            tool => {
+                println!("│   {} - {} lock", "Executing".purple(), tool);
+
+                let mut command = std::process::Command::new(tool);
+                command.arg("lock");
+                command.current_dir(&self.projectpath);
+
+                run_command(command, tool)
+            }
+        }
+    }
+}
+
+impl ReInitializer for Lock {
+    fn initialize(&self) -> ReInitializerResult {
+        if let Some(result) = check_file(&self.file()) {
+            return result;
+        }
+
+        self.reinitialize()
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        match self.lock() {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl Maker for Lock {
+    fn name(&self) -> &'static str {
+        "lock"
+    }
+
+    fn title(&self) -> &'static str {
+        "Locking dependencies ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["venv", "metadata"]
+    }
+}
+
+/// Initial commit maker; commits the generated files after all other steps
+#[derive(Debug)]
+pub struct Commit {
+    /// Paths which aren't committed; the virtual environment and tool caches
+    exclude: Vec<String>,
+    backend: Box<dyn GitBackend>,
+
+    /// Git repository which the generated files are committed to
+    projectpath: PathBuf,
+}
+
+impl TryFrom<&arguments::Arguments> for Commit {
+    type Error = String;
+
+    fn try_from(value: &arguments::Arguments) -> Result<Self, Self::Error> {
+        let mut exclude: Vec<String> = vec![
+            String::from("**/__pycache__/**"),
+            String::from(".pytest_cache"),
+            String::from(".mypy_cache"),
+            String::from(".ruff_cache"),
+        ];
+
+        // a virtual environment outside of the project isn't in the repository
+        if let Some(venv) = venv_in_project(value) {
+            if venv.as_os_str().is_empty() {
+                // the project directory itself is the virtual environment
+                exclude.extend(
+                    [
+                        "bin",
+                        "lib",
+                        "lib64",
+                        "include",
+                        "Lib",
+                        "Include",
+                        "Scripts",
+                        "pyvenv.cfg",
+                    ]
+                    .map(String::from),
+                );
+            } else {
+                exclude.push(venv.display().to_string());
+            }
+        }
+
+        Ok(Commit {
+            exclude,
+            backend: git::backend(),
+            projectpath: PathBuf::new(),
+        })
+    }
+}
+
+impl Commit {
+    fn commit(&self) -> Result<(), String> {
+        self.backend.add_all(&self.projectpath, &self.exclude)?;
+        self.backend.commit(&self.projectpath, "Initial commit")
+    }
+}
+
+impl ReInitializer for Commit {
+    fn initialize(&self) -> ReInitializerResult {
+        if self.backend.has_commits(&self.projectpath) {
+            return ReInitializerResult::Warning(String::from("repository has commits already"));
+        }
+
+        self.reinitialize()
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        match self.commit() {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+}
+
+impl Maker for Commit {
+    fn name(&self) -> &'static str {
+        "commit"
+    }
+
+    fn title(&self) -> &'static str {
+        "Creating initial commit ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        // every step which creates files
+        &[
+            "git",
+            "project",
+            "metadata",
+            "package",
+            "readme",
+            "license",
+            "lint",
+            "typecheck",
+            "pre-commit",
+            "ci",
+            "docker",
+            "tests",
+            "venv",
+            "self-check",
+            "lock",
+        ]
+    }
+}
+
+/// Workspace maker; registers `packages/<name>` in the workspace configuration of the root
+/// project
+#[derive(Debug)]
+pub struct Workspace {
+    /// `uv`, `poetry`, `pdm` or `hatch`
+    tool: String,
+    name: String,
+    root: PathBuf,
+}
+
+impl Workspace {
+    /// Table header and the entry which registers the package; uv and hatch include all
+    /// packages by a glob, poetry and pdm need an entry per package
+    fn entry(&self) -> (&'static str, String) {
+        match self.tool.as_str() {
+            "poetry" => (
+                "[tool.poetry.group.workspace.dependencies]",
+                format!(
+                    "{:?} = {{ path = \"packages/{}\", develop = true }}",
+                    self.name, self.name
+                ),
+            ),
+            "pdm" => (
+                "[tool.pdm.dev-dependencies]",
+                format!(
+                    "{:?} = [\"-e file:///${{PROJECT_ROOT}}/packages/{}\"]",
+                    self.name, self.name
+                ),
+            ),
+            "hatch" => (
+                "[tool.hatch.envs.default.workspace]",
+                String::from("members = [\"packages/*\"]"),
+            ),
+            _ => (
+                "[tool.uv.workspace]",
+                String::from("members = [\"packages/*\"]"),
+            ),
+        }
+    }
+
+    fn register(&self) -> Result<(), String> {
+        let path = self.root.join("pyproject.toml");
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+        let (header, entry) = self.entry();
+
+        if content.lines().any(|line| line.trim() == entry) {
+            println!("│   Package is already registered: {}", path.display());
+            return Ok(());
+        }
+
+        let updated = match content.lines().position(|line| line.trim() == header) {
+            Some(index) => {
+                let mut lines: Vec<&str> = content.lines().collect();
+
+                // the entry is added at the end of the table, before the blank lines
+                let mut end = lines[index + 1..]
+                    .iter()
+                    .position(|line| line.trim_start().starts_with('['))
+                    .map_or(lines.len(), |p| index + 1 + p);
+
+                while end > index + 1 && lines[end - 1].trim().is_empty() {
+                    end -= 1;
+                }
+
+                lines.insert(end, &entry);
+                lines.join("\n") + "\n"
+            }
+            None => format!("{}\n\n{}\n{}\n", content.trim_end(), header, entry),
+        };
+
+        // e.g. `members` is already defined without the `packages/*` glob
+        if let Err(e) = updated.parse::<toml::Table>() {
+            return Err(format!(
+                "cannot register the package in {}; add it to {} manually: {}",
+                path.display(),
+                header,
+                e.message()
+            ));
+        }
+
+        println!("│   Registering package in: {} {}", path.display(), header);
+
+        std::fs::write(&path, updated)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+}
+
+impl ReInitializer for Workspace {
+    fn initialize(&self) -> ReInitializerResult {
+        match self.register() {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.initialize()
+    }
+}
+
+impl Maker for Workspace {
+    fn name(&self) -> &'static str {
+        "workspace"
+    }
+
+    fn title(&self) -> &'static str {
+        "Registering workspace package ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["metadata"]
+    }
+}
+
+/// Returns the virtual environment of `root`; `root` itself, or a directory in it which
+/// contains `pyvenv.cfg`
+pub(crate) fn find_venv(root: &Path) -> Option<PathBuf> {
+    if root.join("pyvenv.cfg").is_file() {
+        return Some(root.to_path_buf());
+    }
+
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(root)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join("pyvenv.cfg").is_file())
+        .collect();
+
+    candidates.sort();
+    candidates.into_iter().next()
+}
+
+/// Editable install maker; installs the package into the virtual environment of the root
+/// project
+#[derive(Debug)]
+pub struct EditableInstall {
+    /// Detected virtual environment; nothing is installed without it
+    venv: Option<PathBuf>,
+
+    /// Package directory, e.g. `packages/<name>`
+    projectpath: PathBuf,
+}
+
+impl EditableInstall {
+    fn install(&self, venv: &Path) -> Result<(), String> {
+        let python = venv_python(venv);
+
+        println!(
+            "│   {} - {} -m pip install -e {}",
+            "Executing".purple(),
+            python.display(),
+            self.projectpath.display()
+        );
+
+        let mut command = std::process::Command::new(&python);
+        command.args(["-m", "pip", "install", "--quiet", "-e"]);
+        command.arg(&self.projectpath);
+
+        run_command(command, &python.display().to_string())
+    }
+}
+
+impl ReInitializer for EditableInstall {
+    fn initialize(&self) -> ReInitializerResult {
+        let venv = match &self.venv {
+            Some(venv) => venv,
+            None => {
+                println!(
+                    "│   {} no virtual environment is found (pyvenv.cfg)",
+                    "warning:".yellow()
+                );
+                return ReInitializerResult::Ok;
+            }
+        };
+
+        match self.install(venv) {
+            Ok(_) => ReInitializerResult::Ok,
+            Err(e) => ReInitializerResult::Err(e),
+        }
+    }
+
+    fn reinitialize(&self) -> ReInitializerResult {
+        self.initialize()
+    }
+}
+
+impl Maker for EditableInstall {
+    fn name(&self) -> &'static str {
+        "install"
+    }
+
+    fn title(&self) -> &'static str {
+        "Installing package ..."
+    }
+
+    fn dependencies(&self) -> &'static [&'static str] {
+        &["project", "metadata", "package", "readme", "workspace"]
+    }
+
+    fn summary(&self) -> Option<String> {
+        match &self.venv {
+            Some(venv) => Some(format!("installed into {}", venv.display())),
+            None => Some(String::from(
+                "not installed; no virtual environment is found",
+            )),
+        }
+    }
+}
+
+impl TryFrom<arguments::Arguments> for Pipeline {
+    type Error = String;
+
+    fn try_from(mut value: arguments::Arguments) -> Result<Self, Self::Error> {
+        // in place projects are named after the current directory
+        if value.project.in_place && value.project.name.is_empty() {
+            value.project.name = match std::env::current_dir() {
+                Ok(path) => path.display().to_string(),
+                Err(e) => {
+                    return Err(format!("cannot read current directory: {}", e));
+                }
+            };
+        }
+
+        let manager = match &mut value.venv {
+            Some(v_args) if !v_args.manager.is_empty() && v_args.manager != "pip" => {
+                // project managers are configured to create the environment in the project
+                v_args.path = String::from(".venv");
+                v_args.manager.clone()
             }
+            _ => String::from("pip"),
         };
 
-        let mut result = MakersStructure {
-            project,
-            git: None,
-            venv: None,
+        if value.tools.docker == "auto" {
+            value.tools.docker = if manager == "poetry" {
+                manager.clone()
+            } else {
+                String::from("pip")
+            };
+        }
+
+        // pyproject.toml is written only for the packaging metadata which is given, and the
+        // steps which are configured in it, or use it
+        let needs_metadata = !value.project.author.is_empty()
+            || !value.project.license.is_empty()
+            || !value.project.description.is_empty()
+            || manager != "pip"
+            || !(value.tools.tests.is_empty() || value.tools.tests == "none")
+            || !value.tools.lint.is_empty()
+            || !value.tools.typecheck.is_empty()
+            || !value.tools.ci.is_empty()
+            || !value.tools.docker.is_empty()
+            || !value.tools.lock.is_empty();
+
+        // author defaults to the git identity
+        if value.project.author.is_empty() {
+            value.project.author = match &value.git {
+                Some(g_args) if !g_args.user_name.is_empty() => g_args.user_name.clone(),
+                _ => git_config_value("user.name").unwrap_or_default(),
+            };
+        }
+
+        if value.project.email.is_empty() {
+            value.project.email = match &value.git {
+                Some(g_args) if !g_args.user_email.is_empty() => g_args.user_email.clone(),
+                _ => git_config_value("user.email").unwrap_or_default(),
+            };
+        }
+
+        let license: Option<License> = if value.project.license.is_empty() {
+            None
+        } else {
+            Some(License::try_from(value.project.clone())?)
         };
 
-        if let Some(g_args) = value.1 {
-            match Git::try_from(g_args) {
-                Ok(mut o) => {
-                    o.projectpath = result.project.full_path();
-                    result.git = Some(o);
-                }
-                Err(e) => {
-                    return Err(e);
-                }
-            }
+        // `python3` is used, unless a version is specified
+        let interpreter = if value.project.python.is_empty() {
+            value.project.python = python_version("python3").unwrap_or_else(|| String::from("3"));
+            String::from("python3")
+        } else {
+            format!("python{}", value.project.python)
+        };
+
+        let metadata: Option<Metadata> = if needs_metadata {
+            Some(Metadata::try_from(&value)?)
+        } else {
+            None
+        };
+
+        let mut package = Package::try_from(&value)?;
+        let mut readme = Readme::try_from(&value)?;
+        let packages = dev_dependencies(&value);
+
+        if value.venv.as_ref().is_some_and(|v| v.without_pip) && !packages.is_empty() {
+            return Err(format!(
+                "{} cannot be installed without pip; remove '--without-pip'",
+                packages.join(", ")
+            ));
         }
 
-        if let Some(v_args) = value.2 {
-            match VirtualEnvironment::try_from(v_args) {
-                Ok(o) => {
-                    result.venv = Some(o);
-                }
-                Err(e) => {
-                    return Err(e);
+        let docker: Option<Docker> = if value.tools.docker.is_empty() {
+            None
+        } else {
+            Some(Docker::try_from(&value)?)
+        };
+
+        let ci: Option<Ci> = if value.tools.ci.is_empty() {
+            None
+        } else {
+            Some(Ci::try_from(&value)?)
+        };
+
+        let pre_commit: Option<PreCommit> = if value.tools.pre_commit {
+            Some(PreCommit::try_from(&value)?)
+        } else {
+            None
+        };
+
+        // mypy is configured in pyproject.toml
+        let typecheck: Option<TypeCheck> = if value.tools.typecheck == "pyright" {
+            Some(TypeCheck::try_from(&value)?)
+        } else {
+            None
+        };
+
+        // other presets are configured in pyproject.toml
+        let lint: Option<Lint> = if value.tools.lint == "flake8" {
+            Some(Lint::try_from(&value)?)
+        } else {
+            None
+        };
+
+        let lock: Option<Lock> = if value.tools.lock.is_empty() {
+            None
+        } else {
+            Some(Lock::try_from(&value)?)
+        };
+
+        let commit: Option<Commit> = match &value.git {
+            Some(g_args) if g_args.commit => Some(Commit::try_from(&value)?),
+            _ => None,
+        };
+
+        let checks: Option<(Tests, SelfCheck)> =
+            if value.tools.tests.is_empty() || value.tools.tests == "none" {
+                None
+            } else {
+                Some((Tests::try_from(&value)?, SelfCheck::try_from(&value)?))
+            };
+
+        let in_place = value.project.in_place;
+        let project: Project = Project::try_from(value.project)?;
+        let projectpath = project.full_path();
+
+        let mut hooks = Hooks {
+            pre_create: value.hooks.pre_create,
+            post_create: value.hooks.post_create,
+            base: project.base.clone(),
+            projectpath: projectpath.clone(),
+            env: vec![
+                ("CREATEPY_PROJECT_NAME", project.name.clone()),
+                ("CREATEPY_PROJECT_DIR", absolute(&projectpath)),
+                ("CREATEPY_PACKAGE_NAME", project.package_name()),
+            ],
+        };
+
+        let mut result = Pipeline::new();
+        result.register(Box::new(project))?;
+
+        if let Some(mut metadata) = metadata {
+            metadata.projectpath = projectpath.clone();
+            result.register(Box::new(metadata))?;
+        }
+
+        package.projectpath = projectpath.clone();
+        result.register(Box::new(package))?;
+
+        readme.projectpath = projectpath.clone();
+        readme.interpreter = interpreter.clone();
+        result.register(Box::new(readme))?;
+
+        if let Some(mut license) = license {
+            license.projectpath = projectpath.clone();
+            result.register(Box::new(license))?;
+        }
+
+        if let Some(g_args) = value.git {
+            let mut git = Git::try_from(g_args)?;
+            git.projectpath = projectpath.clone();
+            result.register(Box::new(git))?;
+        }
+
+        if let Some(mut lint) = lint {
+            lint.projectpath = projectpath.clone();
+            result.register(Box::new(lint))?;
+        }
+
+        if let Some(mut docker) = docker {
+            docker.projectpath = projectpath.clone();
+            result.register(Box::new(docker))?;
+        }
+
+        if let Some(mut ci) = ci {
+            ci.projectpath = projectpath.clone();
+            result.register(Box::new(ci))?;
+        }
+
+        let mut python = PathBuf::from(&interpreter);
+        let mut venvpath: Option<PathBuf> = None;
+
+        if let Some(v_args) = value.venv {
+            let mut venv = VirtualEnvironment::try_from(v_args)?;
+            // project managers create `.venv` in the project; otherwise the path is relative
+            // to the current directory
+            if manager != "pip" {
+                venv.path = projectpath.join(venv.path);
+            }
+            venv.projectpath = projectpath.clone();
+            venv.python = interpreter.clone();
+            venv.packages = packages;
+
+            // an existing virtual environment of the project is used, instead of creating one
+            if in_place {
+                if let Some(found) = find_venv(&projectpath) {
+                    venv.path = found;
                 }
             }
+            python = venv_python(&venv.path);
+            python = std::path::absolute(&python).unwrap_or(python);
+
+            if let Some(mut pre_commit) = pre_commit {
+                pre_commit.venvpath = venv.path.clone();
+                pre_commit.projectpath = projectpath.clone();
+                result.register(Box::new(pre_commit))?;
+            }
+
+            hooks.env.push(("CREATEPY_VENV_PATH", absolute(&venv.path)));
+            venvpath = Some(venv.path.clone());
+            result.register(Box::new(venv))?;
+        }
+
+        if let Some(mut typecheck) = typecheck {
+            typecheck.venv = venvpath;
+            typecheck.projectpath = projectpath.clone();
+            result.register(Box::new(typecheck))?;
+        }
+
+        if let Some((mut tests, mut check)) = checks {
+            tests.projectpath = projectpath.clone();
+            result.register(Box::new(tests))?;
+
+            check.projectpath = projectpath.clone();
+            check.python = python.clone();
+            result.register(Box::new(check))?;
+        }
+
+        if let Some(mut lock) = lock {
+            lock.projectpath = projectpath.clone();
+            lock.python = python;
+            result.register(Box::new(lock))?;
+        }
+
+        if let Some(mut commit) = commit {
+            commit.projectpath = projectpath.clone();
+            result.register(Box::new(commit))?;
+        }
+
+        result.hooks = hooks;
+        result.in_place = in_place;
+        Ok(result)
+    }
+}
+
+impl TryFrom<arguments::PackageArguments> for Pipeline {
+    type Error = String;
+
+    fn try_from(value: arguments::PackageArguments) -> Result<Self, Self::Error> {
+        if value.name.is_empty() || value.name == "." {
+            return Err(String::from("package name cannot be empty or '.'"));
+        }
+
+        if value.name.contains(['/', '\\']) {
+            return Err(format!(
+                "package name cannot contain a path separator: {}",
+                value.name
+            ));
+        }
+
+        let root = if value.root.is_empty() {
+            PathBuf::from(".")
+        } else {
+            PathBuf::from(&value.root)
+        };
+
+        let pyproject = root.join("pyproject.toml");
+
+        let content = std::fs::read_to_string(&pyproject).map_err(|_| {
+            format!(
+                "not a python project, pyproject.toml is not found: {}",
+                root.display()
+            )
+        })?;
+
+        let metadata: toml::Table = content
+            .parse()
+            .map_err(|e| format!("invalid {}: {}", pyproject.display(), e))?;
+
+        // the workspace tool is the project manager of the root project; uv otherwise
+        let tool = match metadata.get("tool").and_then(|t| t.as_table()) {
+            Some(t) if t.contains_key("poetry") => "poetry",
+            Some(t) if t.contains_key("pdm") => "pdm",
+            Some(t) if t.contains_key("hatch") => "hatch",
+            _ => "uv",
+        };
+
+        let mut args = arguments::Arguments::new(value.name.clone());
+        args.project.description = value.description;
+        args.project.layout = String::from("src");
+        args.project.readme_format = String::from("md");
+        args.project.author = git_config_value("user.name").unwrap_or_default();
+        args.project.email = git_config_value("user.email").unwrap_or_default();
+
+        // packages require the python version of the root project
+        let requires_python = metadata
+            .get("project")
+            .and_then(|p| p.get("requires-python"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        args.project.python = match &requires_python {
+            Some(specifier) => python_lower_bound(specifier).ok_or_else(|| {
+                format!(
+                    "cannot find the minimum python version in requires-python of {}: '{}'",
+                    pyproject.display(),
+                    specifier
+                )
+            })?,
+            None => python_version("python3").unwrap_or_else(|| String::from("3")),
+        };
+
+        let project = Project {
+            name: value.name.clone(),
+            base: root.join("packages"),
+            in_place: false,
+        };
+        let projectpath = project.full_path();
+
+        let mut metadata = Metadata::try_from(&args)?;
+        metadata.projectpath = projectpath.clone();
+
+        if let Some(specifier) = requires_python {
+            metadata.requires_python = specifier;
+        }
+
+        let mut package = Package::try_from(&args)?;
+        package.projectpath = projectpath.clone();
+
+        let mut readme = Readme::try_from(&args)?;
+        readme.projectpath = projectpath.clone();
+        readme.interpreter = format!("python{}", args.project.python);
+
+        let mut result = Pipeline::new();
+        result.register(Box::new(project))?;
+        result.register(Box::new(metadata))?;
+        result.register(Box::new(package))?;
+        result.register(Box::new(readme))?;
+
+        let venv = find_venv(&root);
+
+        let mut env = vec![
+            ("CREATEPY_PROJECT_NAME", value.name.clone()),
+            ("CREATEPY_PROJECT_DIR", absolute(&projectpath)),
+            ("CREATEPY_PACKAGE_NAME", package_name(&value.name)),
+        ];
+
+        if let Some(venv) = &venv {
+            env.push(("CREATEPY_VENV_PATH", absolute(venv)));
         }
 
+        result.hooks = Hooks {
+            pre_create: value.hooks.pre_create,
+            post_create: value.hooks.post_create,
+            // `packages` may not exist before the project step
+            base: root.clone(),
+            projectpath: projectpath.clone(),
+            env,
+        };
+
+        result.register(Box::new(Workspace {
+            tool: String::from(tool),
+            name: value.name,
+            root: root.clone(),
+        }))?;
+
+        result.register(Box::new(EditableInstall { venv, projectpath }))?;
+
         Ok(result)
     }
 }
 
-#[inline(always)]
-fn confirm() -> bool {
-    std::io::stdout().flush().unwrap();
+/// Returns the minimum python version of a `requires-python` specifier as
+/// `<major>[.<minor>]`; e.g. `3.11` for `>=3.11.2,<4` or `~=3.11`
+fn python_lower_bound(specifier: &str) -> Option<String> {
+    specifier.split(',').find_map(|clause| {
+        let clause = clause.trim();
+        let version = ["~=", ">=", "=="]
+            .iter()
+            .find_map(|operator| clause.strip_prefix(operator))?
+            .trim();
+        let version = version.strip_suffix(".*").unwrap_or(version);
+
+        let parts: Vec<&str> = version.split('.').collect();
+        if parts
+            .iter()
+            .any(|p| p.is_empty() || !p.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return None;
+        }
+
+        Some(parts[..parts.len().min(2)].join("."))
+    })
+}
 
-    let mut buf = String::new();
-    std::io::stdin().read_line(&mut buf).unwrap();
+/// Absolute form of `path`, without requiring it to exist
+fn absolute(path: &std::path::Path) -> String {
+    std::path::absolute(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
 
-    buf = buf.trim().to_lowercase().to_string();
+/// Confirmation callback; receives the warning message and returns whether the maker
+/// have to be reinitialized
+pub type Prompt = dyn FnMut(&str) -> bool;
 
-    buf == "y" || buf == "yes"
+/// Like [`call_reinitializer`], but nothing is reinitialized; the warning is reported, and
+/// the step is skipped
+pub fn call_initializer(re_t: &dyn ReInitializer) -> Result<(), String> {
+    match re_t.initialize() {
+        ReInitializerResult::Ok => Ok(()),
+        ReInitializerResult::Warning(warn) => {
+            println!("│   {} {}; skipped", "warning:".yellow(), warn);
+            Ok(())
+        }
+        ReInitializerResult::Err(e) => Err(e),
+    }
 }
 
-pub fn call_reinitializer(re_t: &impl ReInitializer, no_input: bool) -> Result<(), String> {
+pub fn call_reinitializer(
+    re_t: &dyn ReInitializer,
+    no_input: bool,
+    prompt: &mut Prompt,
+) -> Result<(), String> {
     let mut status = re_t.initialize();
 
     if let ReInitializerResult::Warning(warn) = status {
+        println!("│   {} {}", "warning:".yellow(), warn);
+
         if no_input {
-            println!("│   {} {}\n│   do you to create it again {}? yes", "warning:".yellow(), warn, "(y/n)".bold());
-        } else {
-            print!("│   {} {}\n│   do you to create it again {}? ", "warning:".yellow(), warn, "(y/n)".bold());
-        }
-        
-        if !no_input && !confirm() {
+            println!("│   do you to create it again {}? yes", "(y/n)".bold());
+        } else if !prompt(&warn) {
             return Ok(());
         }
 
@@ -545,3 +3706,588 @@ pub fn call_reinitializer(re_t: &impl ReInitializer, no_input: bool) -> Result<(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::summary_line;
+
+    /// Returns an empty temporary directory, unique to this process and `name`
+    fn temp_dir(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("createpy-test-{}-{}", name, std::process::id()));
+
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    /// perform `git <args>` command in `path`, and returns its output
+    fn git(path: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        String::from(String::from_utf8_lossy(&output.stdout).trim())
+    }
+
+    #[test]
+    fn git_adds_local_submodule() {
+        let base = temp_dir("submodule");
+        let remote = base.join("lib.git");
+        let work = base.join("work");
+        let project = base.join("project");
+
+        // a bare repository with one commit on `main`
+        git(&base, &["init", "--quiet", "--bare", "lib.git"]);
+        git(&remote, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+        git(&base, &["init", "--quiet", "work"]);
+        std::fs::write(work.join("README.md"), "lib\n").unwrap();
+        git(&work, &["add", "README.md"]);
+        git(&work, &["commit", "--quiet", "-m", "initial commit"]);
+        git(
+            &work,
+            &[
+                "push",
+                "--quiet",
+                remote.to_str().unwrap(),
+                "HEAD:refs/heads/main",
+            ],
+        );
+        let commit = git(&work, &["rev-parse", "--short", "HEAD"]);
+
+        let url = format!("file://{}", remote.display());
+        let mut args = arguments::GitArguments::new();
+        args.submodules.push(format!("lib={}", url));
+
+        std::fs::create_dir(&project).unwrap();
+        let mut maker = Git::try_from(args).unwrap();
+        maker.projectpath = project.clone();
+
+        assert!(matches!(maker.initialize(), ReInitializerResult::Ok));
+
+        let gitmodules = std::fs::read_to_string(project.join(".gitmodules")).unwrap();
+        assert!(gitmodules.contains("[submodule \"lib\"]"));
+        assert!(gitmodules.contains("path = lib"));
+        assert!(gitmodules.contains(&format!("url = {}", url)));
+
+        assert!(project.join("lib").join("README.md").is_file());
+        assert_eq!(
+            git(&project.join("lib"), &["rev-parse", "--short", "HEAD"]),
+            commit
+        );
+
+        assert_eq!(
+            summary_line(&maker),
+            Some(format!("git: submodules: lib ({})", commit))
+        );
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn ref_name_valid() {
+        for name in [
+            "main",
+            "feature/login",
+            "release-1.0",
+            "v1.2.3",
+            "user@host",
+        ] {
+            assert_eq!(ref_name_error(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn ref_name_invalid() {
+        let cases = [
+            ("a..b", "it cannot contain '..' or '@{'"),
+            ("-main", "it cannot start with '-'"),
+            ("main@{1}", "it cannot contain '..' or '@{'"),
+            (
+                "main.lock",
+                "its components cannot start with '.' or end with '.lock'",
+            ),
+            (
+                "feature/x.lock",
+                "its components cannot start with '.' or end with '.lock'",
+            ),
+            ("feature/", "it cannot have empty components"),
+            ("/main", "it cannot have empty components"),
+            ("a//b", "it cannot have empty components"),
+            (
+                "feature/.hidden",
+                "its components cannot start with '.' or end with '.lock'",
+            ),
+            ("main.", "it cannot end with '.'"),
+            ("HEAD", "it's reserved"),
+            ("@", "it's reserved"),
+        ];
+
+        for (name, reason) in cases {
+            assert_eq!(ref_name_error(name), Some(reason), "{}", name);
+        }
+
+        for name in [
+            "a\tb", "a\nb", "a\u{7f}b", "a b", "a~1", "a^", "a:b", "a?", "a*", "a[b", "a\\b",
+        ] {
+            assert_eq!(
+                ref_name_error(name),
+                Some("it cannot contain spaces, control characters or any of '~^:?*[\\'"),
+                "{:?}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn branch_and_remote_names() {
+        assert_eq!(check_branch_name("main"), Ok(()));
+        assert_eq!(
+            check_branch_name("-main"),
+            Err(String::from(
+                "invalid branch name: '-main'; it cannot start with '-'"
+            ))
+        );
+
+        assert_eq!(check_remote_name("upstream"), Ok(()));
+        assert_eq!(
+            check_remote_name(""),
+            Err(String::from("invalid remote name: ''; it cannot be empty"))
+        );
+    }
+
+    /// Register package `name` in a `pyproject.toml` with `content`; returns the updated file
+    fn register(tool: &str, name: &str, content: &str) -> Result<String, String> {
+        let root = temp_dir(&format!("workspace-{}-{}", tool, name));
+        std::fs::write(root.join("pyproject.toml"), content).unwrap();
+
+        let workspace = Workspace {
+            tool: String::from(tool),
+            name: String::from(name),
+            root: root.clone(),
+        };
+
+        let result = workspace
+            .register()
+            .map(|_| std::fs::read_to_string(root.join("pyproject.toml")).unwrap());
+
+        std::fs::remove_dir_all(&root).unwrap();
+        result
+    }
+
+    #[test]
+    fn workspace_adds_table() {
+        let content = "[project]\nname = \"demo\"\n\n";
+
+        let cases = [
+            (
+                "uv",
+                "[tool.uv.workspace]\nmembers = [\"packages/*\"]\n",
+            ),
+            (
+                "poetry",
+                "[tool.poetry.group.workspace.dependencies]\n\"core\" = { path = \"packages/core\", develop = true }\n",
+            ),
+            (
+                "pdm",
+                "[tool.pdm.dev-dependencies]\n\"core\" = [\"-e file:///${PROJECT_ROOT}/packages/core\"]\n",
+            ),
+            (
+                "hatch",
+                "[tool.hatch.envs.default.workspace]\nmembers = [\"packages/*\"]\n",
+            ),
+        ];
+
+        for (tool, table) in cases {
+            assert_eq!(
+                register(tool, "core", content),
+                Ok(format!("[project]\nname = \"demo\"\n\n{}", table)),
+                "{}",
+                tool
+            );
+        }
+    }
+
+    #[test]
+    fn workspace_extends_table_with_blank_lines() {
+        let content =
+            "[project]\nname = \"demo\"\n\n[tool.uv.workspace]\nexclude = [\"packages/old\"]\n\n\n";
+
+        assert_eq!(
+            register("uv", "core", content),
+            Ok(String::from(
+                "[project]\nname = \"demo\"\n\n[tool.uv.workspace]\nexclude = [\"packages/old\"]\nmembers = [\"packages/*\"]\n\n\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn workspace_extends_table_before_next_table() {
+        let content = "[tool.poetry.group.workspace.dependencies]\n\"core\" = { path = \"packages/core\", develop = true }\n\n[tool.ruff]\nline-length = 100\n";
+
+        assert_eq!(
+            register("poetry", "api", content),
+            Ok(String::from(
+                "[tool.poetry.group.workspace.dependencies]\n\"core\" = { path = \"packages/core\", develop = true }\n\"api\" = { path = \"packages/api\", develop = true }\n\n[tool.ruff]\nline-length = 100\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn workspace_keeps_registered_package() {
+        let cases = [
+            ("uv", "[tool.uv.workspace]\nmembers = [\"packages/*\"]\n"),
+            ("hatch", "[tool.hatch.envs.default.workspace]\nmembers = [\"packages/*\"]\n"),
+            ("poetry", "[tool.poetry.group.workspace.dependencies]\n\"core\" = { path = \"packages/core\", develop = true }\n"),
+            ("pdm", "[tool.pdm.dev-dependencies]\n\"core\" = [\"-e file:///${PROJECT_ROOT}/packages/core\"]\n"),
+        ];
+
+        for (tool, content) in cases {
+            assert_eq!(
+                register(tool, "core", content),
+                Ok(String::from(content)),
+                "{}",
+                tool
+            );
+        }
+    }
+
+    #[test]
+    fn workspace_rejects_invalid_result() {
+        let content = "[tool.uv.workspace]\nmembers = [\"libs/*\"]\n";
+
+        let error = register("uv", "core", content).unwrap_err();
+        assert!(
+            error.contains("add it to [tool.uv.workspace] manually"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn absolute_venv_path() {
+        let base = temp_dir("absolute-venv");
+        let project = base.join("project");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(project.join("main.py"), "").unwrap();
+
+        git(&project, &["init", "--quiet"]);
+        git(&project, &["config", "user.name", "test"]);
+        git(&project, &["config", "user.email", "test@example.com"]);
+
+        let mut value = arguments::Arguments::new(project.display().to_string());
+        value.tools.lint = String::from("flake8");
+        value.tools.docker = String::from("pip");
+
+        // outside of the project, nothing is excluded
+        let mut v_args = arguments::VirtualEnvironmentArguments::new();
+        v_args.path = base.join("venv").display().to_string();
+        value.venv = Some(v_args.clone());
+
+        let lint = Lint::try_from(&value).unwrap();
+        let docker = Docker::try_from(&value).unwrap();
+        let mut commit = Commit::try_from(&value).unwrap();
+
+        assert_eq!(lint.excludes, vec![".git"]);
+        assert!(!docker.ignores.iter().any(|i| i.contains("venv")));
+        assert!(!commit.exclude.iter().any(|e| e.contains("venv")));
+
+        commit.projectpath = project.clone();
+        assert!(matches!(commit.initialize(), ReInitializerResult::Ok));
+        assert_eq!(git(&project, &["ls-files"]), "main.py");
+
+        // in the project, it's excluded by its relative path
+        v_args.path = project.join("env").display().to_string();
+        value.venv = Some(v_args);
+
+        std::fs::create_dir(project.join("env")).unwrap();
+        std::fs::write(project.join("env").join("pyvenv.cfg"), "").unwrap();
+        std::fs::write(project.join("lib.py"), "").unwrap();
+
+        let lint = Lint::try_from(&value).unwrap();
+        let docker = Docker::try_from(&value).unwrap();
+        commit = Commit::try_from(&value).unwrap();
+        commit.projectpath = project.clone();
+
+        assert_eq!(lint.excludes, vec![".git", "env"]);
+        assert!(docker.ignores.contains(&String::from("env")));
+        assert!(matches!(commit.reinitialize(), ReInitializerResult::Ok));
+
+        assert_eq!(git(&project, &["ls-files"]), "lib.py\nmain.py");
+        assert_eq!(git(&project, &["status", "--porcelain"]), "?? env/");
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn ci_service_of_existing_repository() {
+        let project = temp_dir("ci-remotes");
+        git(&project, &["init", "--quiet"]);
+
+        let mut value = arguments::Arguments::new(project.display().to_string());
+        value.project.in_place = true;
+        value.tools.ci = String::from("auto");
+
+        assert!(Ci::try_from(&value).is_err());
+
+        git(
+            &project,
+            &[
+                "remote",
+                "add",
+                "backup",
+                "https://example.com/owner/repo.git",
+            ],
+        );
+        git(
+            &project,
+            &["remote", "add", "origin", "git@github.com:owner/repo.git"],
+        );
+        assert_eq!(Ci::try_from(&value).unwrap().service, "github");
+
+        // remotes of the repository are used only in place
+        value.project.in_place = false;
+        assert!(Ci::try_from(&value).is_err());
+
+        std::fs::remove_dir_all(&project).unwrap();
+    }
+
+    #[test]
+    fn python_lower_bounds() {
+        let cases = [
+            (">=3.11", Some("3.11")),
+            ("~=3.11", Some("3.11")),
+            ("~=3.11.2", Some("3.11")),
+            (">=3.11,<4", Some("3.11")),
+            ("<4, >= 3.12.1", Some("3.12")),
+            ("==3.10.*", Some("3.10")),
+            (">=3", Some("3")),
+            ("<4", None),
+            (">3.10", None),
+            (">=3.x", None),
+            ("", None),
+        ];
+
+        for (specifier, version) in cases {
+            assert_eq!(
+                python_lower_bound(specifier).as_deref(),
+                version,
+                "{}",
+                specifier
+            );
+        }
+    }
+
+    /// Add package `core` to a project which requires `specifier`; returns the package
+    /// `pyproject.toml`
+    fn add_package(specifier: &str) -> Result<String, String> {
+        let root = temp_dir(&format!(
+            "requires-python-{}",
+            specifier.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        ));
+        std::fs::write(
+            root.join("pyproject.toml"),
+            format!(
+                "[project]\nname = \"root\"\nrequires-python = {:?}\n",
+                specifier
+            ),
+        )
+        .unwrap();
+
+        let mut args = arguments::PackageArguments::new(String::from("core"));
+        args.root = root.display().to_string();
+
+        let result = Pipeline::try_from(args).and_then(|pipeline| {
+            pipeline.run(true, &mut |_| false)?;
+            Ok(
+                std::fs::read_to_string(root.join("packages").join("core").join("pyproject.toml"))
+                    .unwrap(),
+            )
+        });
+
+        std::fs::remove_dir_all(&root).unwrap();
+        result
+    }
+
+    #[test]
+    fn package_requires_python() {
+        for specifier in ["~=3.11", ">=3.11,<4", ">=3.12"] {
+            let content = add_package(specifier).unwrap();
+            let expected = format!("requires-python = {:?}\n", specifier);
+            assert!(content.contains(&expected), "{}", content);
+        }
+
+        assert!(add_package("<4").is_err());
+    }
+
+    #[test]
+    fn git_config_keys() {
+        for key in [
+            "pull.rebase",
+            "core.autocrlf",
+            "remote.origin.push-url",
+            "branch.my.feature.merge",
+        ] {
+            assert_eq!(check_config_key(key), Ok(()), "{}", key);
+        }
+
+        for key in [
+            "pull",
+            "pull.",
+            ".rebase",
+            "pull.1rebase",
+            "pull_x.rebase",
+            "pull.re base",
+        ] {
+            assert!(check_config_key(key).is_err(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn git_config_entries() {
+        let mut args = arguments::GitArguments::new();
+        args.config = vec![
+            String::from("pull.rebase = true"),
+            String::from("core.autocrlf=input"),
+            String::from("user.signingkey = "),
+        ];
+
+        let git = Git::try_from(args).unwrap();
+
+        assert_eq!(
+            git.config,
+            vec![
+                (String::from("pull.rebase"), String::from("true")),
+                (String::from("core.autocrlf"), String::from("input")),
+                (String::from("user.signingkey"), String::new()),
+            ]
+        );
+
+        let mut args = arguments::GitArguments::new();
+        args.config = vec![String::from("pull.rebase")];
+        assert!(Git::try_from(args).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn native_venv_layout() {
+        let base = temp_dir("native-venv");
+        let path = base.join("venv");
+
+        let mut args = arguments::VirtualEnvironmentArguments::new();
+        args.script = String::from("native");
+        args.path = path.display().to_string();
+        args.without_pip = true;
+
+        let venv = VirtualEnvironment::try_from(args).unwrap();
+        assert!(matches!(venv.initialize(), ReInitializerResult::Ok));
+
+        let config = std::fs::read_to_string(path.join("pyvenv.cfg")).unwrap();
+        let value = |key: &str| {
+            config
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix(" = "))
+                .map(String::from)
+                .unwrap_or_else(|| panic!("{} is not in pyvenv.cfg: {}", key, config))
+        };
+
+        let executable = PathBuf::from(value("executable"));
+        let version = value("version");
+        let short_version = version.rsplit_once('.').unwrap().0;
+
+        assert_eq!(value("include-system-site-packages"), "false");
+        assert_eq!(PathBuf::from(value("home")), executable.parent().unwrap());
+        assert!(executable.is_absolute());
+
+        for name in ["python", "python3", &format!("python{}", short_version)] {
+            let link = path.join("bin").join(name);
+            assert_eq!(std::fs::read_link(&link).unwrap(), executable, "{}", name);
+        }
+
+        assert!(path
+            .join("lib")
+            .join(format!("python{}", short_version))
+            .join("site-packages")
+            .is_dir());
+
+        let absolute = std::path::absolute(&path).unwrap();
+        for (name, _) in template::ACTIVATE_SCRIPTS {
+            let script = std::fs::read_to_string(path.join("bin").join(name)).unwrap();
+            assert!(script.contains(&absolute.display().to_string()), "{}", name);
+            assert!(!script.contains("{{"), "{}", name);
+        }
+
+        // python finds the environment by `pyvenv.cfg`
+        let mut command = std::process::Command::new(venv_python(&path));
+        command.args(["-c", "import sys; print(sys.prefix)"]);
+        let prefix = command_output(command, "python").unwrap();
+        assert_eq!(
+            std::fs::canonicalize(prefix.trim()).unwrap(),
+            std::fs::canonicalize(&path).unwrap()
+        );
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    /// Descriptions which are escaped differently in TOML, python and rust
+    const TRICKY_TEXTS: [&str; 7] = [
+        "plain text",
+        "say \"hi\"",
+        "ends with a quote\"",
+        "\"\"\" closes the docstring",
+        "back\\slash \\n \\u{41}",
+        "tab\tdelete\u{7f} bell\u{7}",
+        "it's ünïcode ✓",
+    ];
+
+    #[test]
+    fn metadata_strings() {
+        for text in TRICKY_TEXTS {
+            let mut value = arguments::Arguments::new(String::from("demo"));
+            value.project.description = String::from(text);
+            value.project.author = String::from(text);
+            value.project.email = String::from(text);
+
+            let rendered = Metadata::try_from(&value).unwrap().render();
+            let table: toml::Table = rendered
+                .parse()
+                .unwrap_or_else(|e| panic!("{}\n{}", e, rendered));
+            let project = &table["project"];
+
+            assert_eq!(project["description"].as_str(), Some(text));
+            assert_eq!(project["authors"][0]["name"].as_str(), Some(text));
+            assert_eq!(project["authors"][0]["email"].as_str(), Some(text));
+        }
+    }
+
+    #[test]
+    fn package_docstring() {
+        for text in TRICKY_TEXTS {
+            let source = format!(
+                "{}\nimport sys\nsys.stdout.write(__doc__)\n",
+                python_docstring(text)
+            );
+
+            let mut command = std::process::Command::new("python3");
+            command.args(["-c", &source]);
+
+            assert_eq!(
+                command_output(command, "python3").unwrap(),
+                text,
+                "{}",
+                source
+            );
+        }
+    }
+}
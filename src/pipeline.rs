@@ -0,0 +1,392 @@
+use colored::Colorize;
+
+use crate::hooks::Hooks;
+use crate::makers::{call_initializer, call_reinitializer, Maker, Prompt};
+
+/// Names of the built-in steps; they can be selected (or skipped) even if they aren't enabled
+const STEPS: [&str; 18] = [
+    "project",
+    "metadata",
+    "package",
+    "readme",
+    "license",
+    "git",
+    "lint",
+    "docker",
+    "ci",
+    "pre-commit",
+    "venv",
+    "typecheck",
+    "tests",
+    "self-check",
+    "lock",
+    "commit",
+    "workspace",
+    "install",
+];
+
+/// Ordered list of makers, performed in dependency order between pre-create and
+/// post-create hooks
+#[derive(Default)]
+pub struct Pipeline {
+    makers: Vec<Box<dyn Maker>>,
+    pub(crate) hooks: Hooks,
+
+    /// Nothing is reinitialized; existing files, git repository and virtual environment
+    /// are kept, e.g. `createpy init`
+    pub(crate) in_place: bool,
+
+    /// Steps which were registered, but removed by [`Pipeline::select`]
+    deselected: Vec<&'static str>,
+}
+
+impl Pipeline {
+    pub fn new() -> Pipeline {
+        Pipeline {
+            makers: Vec::new(),
+            hooks: Hooks::default(),
+            in_place: false,
+            deselected: Vec::new(),
+        }
+    }
+
+    /// Register a new step; step names must be unique
+    pub fn register(&mut self, maker: Box<dyn Maker>) -> Result<(), String> {
+        if self.contains(maker.name()) {
+            return Err(format!("step is already registered: '{}'", maker.name()));
+        }
+
+        self.makers.push(maker);
+        Ok(())
+    }
+
+    /// Returns `true` if a step named `name` is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.makers.iter().any(|m| m.name() == name)
+    }
+
+    /// Names of the registered steps, in registration order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.makers.iter().map(|m| m.name()).collect()
+    }
+
+    /// Names of the built-in steps, and the other registered steps
+    fn known(&self) -> Vec<&'static str> {
+        let mut names = STEPS.to_vec();
+        names.extend(
+            self.names()
+                .into_iter()
+                .filter(|name| !STEPS.contains(name)),
+        );
+        names
+    }
+
+    /// Keep only the steps in `only` (if it isn't empty), and remove the steps in `skip`;
+    /// known steps which aren't registered are ignored
+    pub fn select(&mut self, only: &[String], skip: &[String]) -> Result<(), String> {
+        let known = self.known();
+
+        for name in only.iter().chain(skip.iter()) {
+            if !known.contains(&name.as_str()) {
+                return Err(format!(
+                    "no such step: '{}' (available steps: {})",
+                    name,
+                    known.join(", ")
+                ));
+            }
+        }
+
+        let selected = |name: &str| {
+            (only.is_empty() || only.iter().any(|n| n == name)) && !skip.iter().any(|n| n == name)
+        };
+
+        self.deselected.extend(
+            self.makers
+                .iter()
+                .map(|m| m.name())
+                .filter(|name| !selected(name)),
+        );
+        self.makers.retain(|m| selected(m.name()));
+
+        Ok(())
+    }
+
+    /// Sort steps so that every step comes after its dependencies; registration order is kept
+    /// for independent steps. Dependencies which aren't registered are ignored; e.g. `git` step
+    /// of a project without git, or a step which isn't selected.
+    fn order(&self) -> Result<Vec<usize>, String> {
+        let mut ordered: Vec<usize> = Vec::with_capacity(self.makers.len());
+        let mut pending: Vec<usize> = (0..self.makers.len()).collect();
+
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|&index| {
+                self.makers[index].dependencies().iter().all(|dep| {
+                    !self.contains(dep) || ordered.iter().any(|&o| self.makers[o].name() == *dep)
+                })
+            });
+
+            match ready {
+                Some(position) => ordered.push(pending.remove(position)),
+                None => {
+                    let names: Vec<&str> = pending.iter().map(|&i| self.makers[i].name()).collect();
+                    return Err(format!(
+                        "dependency cycle between steps: {}",
+                        names.join(", ")
+                    ));
+                }
+            }
+        }
+
+        Ok(ordered)
+    }
+
+    /// Dependencies of `maker` which were removed by [`Pipeline::select`]; they're reported,
+    /// since the step is performed without them. In place, the existing project provides them.
+    fn deselected_dependencies(&self, maker: &dyn Maker) -> Vec<&'static str> {
+        if self.in_place {
+            return Vec::new();
+        }
+
+        maker
+            .dependencies()
+            .iter()
+            .copied()
+            .filter(|dep| self.deselected.contains(dep))
+            .collect()
+    }
+
+    /// Perform all steps in dependency order; progress is printed to stdout
+    pub fn run(&self, no_input: bool, prompt: &mut Prompt) -> Result<(), String> {
+        let order = self.order()?;
+
+        self.hooks.pre_create()?;
+
+        let mut summaries: Vec<String> = Vec::new();
+
+        for index in order {
+            let maker = &self.makers[index];
+
+            println!("{}", maker.title().bold());
+
+            for dep in self.deselected_dependencies(maker.as_ref()) {
+                println!(
+                    "│   {} '{}' step isn't selected; performing without it",
+                    "warning:".yellow(),
+                    dep
+                );
+            }
+
+            let result = if self.in_place {
+                call_initializer(maker.as_ref())
+            } else {
+                call_reinitializer(maker.as_ref(), no_input, prompt)
+            };
+
+            if let Err(e) = result {
+                println!("└── {} {}", "error:".red().bold(), e);
+                return Err(e);
+            }
+
+            println!("└── {}", "END\n".green().bold());
+
+            if let Some(line) = summary_line(maker.as_ref()) {
+                summaries.push(line);
+            }
+        }
+
+        self.hooks.post_create()?;
+
+        if !summaries.is_empty() {
+            println!("{}", "Summary".bold());
+
+            for line in summaries {
+                println!("│   {}", line);
+            }
+
+            println!("└── {}", "END\n".green().bold());
+        }
+
+        Ok(())
+    }
+}
+
+/// Line of the final summary which reports the result of `maker`, e.g.
+/// `git: submodules: lib (1a2b3c4)`
+pub(crate) fn summary_line(maker: &dyn Maker) -> Option<String> {
+    maker
+        .summary()
+        .map(|summary| format!("{}: {}", maker.name(), summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::makers::{ReInitializer, ReInitializerResult};
+
+    /// A step which does nothing
+    struct Step {
+        name: &'static str,
+        dependencies: &'static [&'static str],
+    }
+
+    impl ReInitializer for Step {
+        fn initialize(&self) -> ReInitializerResult {
+            ReInitializerResult::Ok
+        }
+
+        fn reinitialize(&self) -> ReInitializerResult {
+            ReInitializerResult::Ok
+        }
+    }
+
+    impl Maker for Step {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn title(&self) -> &'static str {
+            self.name
+        }
+
+        fn dependencies(&self) -> &'static [&'static str] {
+            self.dependencies
+        }
+    }
+
+    fn pipeline(steps: &[(&'static str, &'static [&'static str])]) -> Pipeline {
+        let mut result = Pipeline::new();
+
+        for &(name, dependencies) in steps {
+            result
+                .register(Box::new(Step { name, dependencies }))
+                .unwrap();
+        }
+
+        result
+    }
+
+    /// Step names in the order which they're performed
+    fn ordered(pipeline: &Pipeline) -> Result<Vec<&'static str>, String> {
+        let order = pipeline.order()?;
+        Ok(order.iter().map(|&i| pipeline.makers[i].name()).collect())
+    }
+
+    fn strings(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| String::from(*name)).collect()
+    }
+
+    #[test]
+    fn register_rejects_duplicates() {
+        let mut p = pipeline(&[("project", &[])]);
+        let step = Step {
+            name: "project",
+            dependencies: &[],
+        };
+
+        assert_eq!(
+            p.register(Box::new(step)),
+            Err(String::from("step is already registered: 'project'"))
+        );
+    }
+
+    #[test]
+    fn order_follows_dependencies() {
+        let p = pipeline(&[
+            ("commit", &["git", "readme"]),
+            ("git", &["project"]),
+            ("readme", &["project"]),
+            ("project", &[]),
+            ("license", &[]),
+        ]);
+
+        assert_eq!(
+            ordered(&p),
+            Ok(vec!["project", "git", "readme", "commit", "license"])
+        );
+    }
+
+    #[test]
+    fn order_keeps_registration_order() {
+        let p = pipeline(&[("b", &[]), ("a", &[]), ("c", &[])]);
+
+        assert_eq!(ordered(&p), Ok(vec!["b", "a", "c"]));
+    }
+
+    #[test]
+    fn order_ignores_unregistered_dependencies() {
+        let p = pipeline(&[("lock", &["venv", "metadata"]), ("metadata", &[])]);
+
+        assert_eq!(ordered(&p), Ok(vec!["metadata", "lock"]));
+    }
+
+    #[test]
+    fn order_rejects_cycles() {
+        let p = pipeline(&[("a", &["b"]), ("b", &["a"]), ("c", &[])]);
+
+        assert_eq!(
+            ordered(&p),
+            Err(String::from("dependency cycle between steps: a, b"))
+        );
+    }
+
+    #[test]
+    fn select_only() {
+        let mut p = pipeline(&[("project", &[]), ("git", &["project"]), ("venv", &[])]);
+
+        p.select(&strings(&["git", "venv"]), &[]).unwrap();
+
+        assert_eq!(p.names(), vec!["git", "venv"]);
+        assert_eq!(
+            p.deselected_dependencies(p.makers[0].as_ref()),
+            vec!["project"]
+        );
+    }
+
+    #[test]
+    fn select_skip() {
+        let mut p = pipeline(&[("project", &[]), ("git", &["project"]), ("venv", &[])]);
+
+        p.select(&[], &strings(&["venv"])).unwrap();
+        assert_eq!(p.names(), vec!["project", "git"]);
+
+        p.select(&strings(&["project", "git"]), &strings(&["git"]))
+            .unwrap();
+        assert_eq!(p.names(), vec!["project"]);
+    }
+
+    #[test]
+    fn select_rejects_unknown_steps() {
+        let mut p = pipeline(&[("project", &[]), ("git", &["project"]), ("docs", &[])]);
+
+        let error = p.select(&strings(&["git", "site"]), &[]).unwrap_err();
+        assert!(error.starts_with("no such step: 'site' (available steps: project, "));
+        // custom steps are listed after the built-in ones
+        assert!(error.ends_with(", install, docs)"), "{}", error);
+
+        assert!(p.select(&[], &strings(&["site"])).is_err());
+
+        // nothing is removed
+        assert_eq!(p.names(), vec!["project", "git", "docs"]);
+    }
+
+    #[test]
+    fn select_ignores_known_steps_which_are_not_registered() {
+        let mut p = pipeline(&[("project", &[]), ("git", &["project"])]);
+
+        p.select(&[], &strings(&["venv", "lock"])).unwrap();
+        assert_eq!(p.names(), vec!["project", "git"]);
+
+        p.select(&strings(&["git", "venv"]), &[]).unwrap();
+        assert_eq!(p.names(), vec!["git"]);
+    }
+
+    #[test]
+    fn deselected_dependencies_in_place() {
+        let mut p = pipeline(&[("project", &[]), ("git", &["project"])]);
+        p.in_place = true;
+
+        p.select(&strings(&["git"]), &[]).unwrap();
+
+        assert!(p.deselected_dependencies(p.makers[0].as_ref()).is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+//! Template rendering, and the templates which are embedded in the binary
+
+/// SPDX license identifiers and their texts
+pub const LICENSES: [(&str, &str); 7] = [
+    ("MIT", include_str!("templates/licenses/MIT.txt")),
+    ("Apache-2.0", include_str!("templates/licenses/Apache-2.0.txt")),
+    ("BSD-3-Clause", include_str!("templates/licenses/BSD-3-Clause.txt")),
+    ("GPL-3.0-only", include_str!("templates/licenses/GPL-3.0-only.txt")),
+    ("MPL-2.0", include_str!("templates/licenses/MPL-2.0.txt")),
+    ("Unlicense", include_str!("templates/licenses/Unlicense.txt")),
+    ("Proprietary", include_str!("templates/licenses/Proprietary.txt")),
+];
+
+/// pre-commit hook repositories of linter presets and type checkers; `base` is always used
+pub const PRE_COMMIT_REPOS: [(&str, &str); 5] = [
+    ("base", include_str!("templates/pre-commit/base.yaml")),
+    ("ruff", include_str!("templates/pre-commit/ruff.yaml")),
+    ("black+isort", include_str!("templates/pre-commit/black+isort.yaml")),
+    ("flake8", include_str!("templates/pre-commit/flake8.yaml")),
+    ("mypy", include_str!("templates/pre-commit/mypy.yaml")),
+];
+
+/// Activation scripts of the virtual environments which are created by `native` script;
+/// `{{venv}}` is the absolute path of the environment and `{{prompt}}` its name
+pub const ACTIVATE_SCRIPTS: [(&str, &str); 2] = [
+    ("activate", include_str!("templates/venv/activate")),
+    (
+        "activate.fish",
+        include_str!("templates/venv/activate.fish"),
+    ),
+];
+
+/// Returns the license text of `id` SPDX identifier
+pub fn license(id: &str) -> Option<&'static str> {
+    LICENSES.iter().find(|(i, _)| *i == id).map(|(_, text)| *text)
+}
+
+/// Replace `{{name}}` placeholders of `text` with the values of `vars`;
+/// unknown placeholders are kept as they are.
+pub fn render(text: &str, vars: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+
+        let key = rest[start + 2..start + 2 + length].trim();
+        result.push_str(&rest[..start]);
+
+        match vars.iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => result.push_str(value),
+            None => result.push_str(&rest[start..start + length + 4]),
+        }
+
+        rest = &rest[start + length + 4..];
+    }
+
+    result.push_str(rest);
+    result
+}
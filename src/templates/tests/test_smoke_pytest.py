@@ -0,0 +1,5 @@
+import {{package}}
+
+
+def test_import():
+    assert {{package}}.__version__
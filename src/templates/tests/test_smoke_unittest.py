@@ -0,0 +1,12 @@
+import unittest
+
+import {{package}}
+
+
+class SmokeTest(unittest.TestCase):
+    def test_import(self):
+        self.assertTrue({{package}}.__version__)
+
+
+if __name__ == "__main__":
+    unittest.main()